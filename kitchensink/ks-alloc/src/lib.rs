@@ -0,0 +1,93 @@
+//! Allocation-counting global allocator for the kitchensink scenario binaries.
+//!
+//! Install [`CountingAllocator`] as the `#[global_allocator]`, then wrap the
+//! code to measure in [`measure`], which prints one tab-separated line to
+//! stdout for limpid to parse:
+//!
+//! ```text
+//! alloc  <label>  <allocations>  <bytes allocated>  <peak live bytes>
+//! ```
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+/// Number of `alloc`/`realloc` calls
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+/// Total bytes requested, counting only the growth of reallocations
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+/// Bytes currently allocated
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+/// High-water mark of `LIVE_BYTES`
+static PEAK_LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// Wraps the system allocator and counts what goes through it
+pub struct CountingAllocator;
+
+fn grow(bytes: usize) {
+    ALLOCATED_BYTES.fetch_add(bytes, Relaxed);
+    let live = LIVE_BYTES.fetch_add(bytes, Relaxed) + bytes;
+    PEAK_LIVE_BYTES.fetch_max(live, Relaxed);
+}
+
+fn shrink(bytes: usize) {
+    LIVE_BYTES.fetch_sub(bytes, Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Relaxed);
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Relaxed);
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Relaxed);
+            if new_size > layout.size() {
+                grow(new_size - layout.size());
+            } else {
+                shrink(layout.size() - new_size);
+            }
+        }
+        new_ptr
+    }
+}
+
+/// Run `f` and print the allocations it made under the given label.
+///
+/// The peak is relative to the live heap when `f` starts.
+pub fn measure<T>(label: &str, f: impl FnOnce() -> T) -> T {
+    let allocations = ALLOCATIONS.load(Relaxed);
+    let allocated_bytes = ALLOCATED_BYTES.load(Relaxed);
+    let live_bytes = LIVE_BYTES.load(Relaxed);
+    PEAK_LIVE_BYTES.store(live_bytes, Relaxed);
+
+    let result = f();
+
+    println!(
+        "alloc\t{label}\t{}\t{}\t{}",
+        ALLOCATIONS.load(Relaxed) - allocations,
+        ALLOCATED_BYTES.load(Relaxed) - allocated_bytes,
+        PEAK_LIVE_BYTES.load(Relaxed).saturating_sub(live_bytes)
+    );
+
+    result
+}
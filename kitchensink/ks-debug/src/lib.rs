@@ -1,5 +1,15 @@
 use ks_types::Catalog;
+use ks_types::config::StoreConfig;
+use ks_types::extra::Marketplace;
 
 pub fn pretty_print(catalog: &Catalog) {
     eprintln!("{:#?}", catalog);
 }
+
+pub fn pretty_print_marketplace(marketplace: &Marketplace) {
+    eprintln!("{:#?}", marketplace);
+}
+
+pub fn pretty_print_store_config(config: &StoreConfig) {
+    eprintln!("{:#?}", config);
+}
@@ -0,0 +1,81 @@
+//! Runtime benchmark harness for the facet JSON scenario.
+//!
+//! Serializes and deserializes the mock catalog (`--scale` times the default
+//! size) in a loop and prints one tab-separated line per measurement to
+//! stdout, which limpid parses:
+//!
+//! ```text
+//! bytes        <size of the serialized JSON>
+//! serialize    <median ns> <min ns> <max ns>
+//! deserialize  <median ns> <min ns> <max ns>
+//! ```
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+fn main() {
+    let mut warmup = 20;
+    let mut iterations = 200;
+    let mut scale = 1;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--warmup" => warmup = parse_count(&arg, args.next()),
+            "--iterations" => iterations = parse_count(&arg, args.next()),
+            "--scale" => scale = parse_count(&arg, args.next()),
+            other => panic!("unknown argument: {other}"),
+        }
+    }
+    assert!(iterations > 0, "--iterations must be at least 1");
+
+    let catalog = ks_mock::generate_catalog(&ks_mock::MockConfig::scaled(scale));
+    let json = ks_facet_json_write::catalog_to_json(&catalog);
+
+    let serialize = measure(warmup, iterations, || {
+        black_box(ks_facet_json_write::catalog_to_json(black_box(&catalog)));
+    });
+    let deserialize = measure(warmup, iterations, || {
+        black_box(ks_facet_json_read::catalog_from_json(black_box(&json)));
+    });
+
+    println!("bytes\t{}", json.len());
+    print_samples("serialize", serialize);
+    print_samples("deserialize", deserialize);
+}
+
+fn parse_count(flag: &str, value: Option<String>) -> usize {
+    value
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_else(|| panic!("{flag} expects a number"))
+}
+
+/// Run `f` `warmup` times untimed, then `iterations` times timed, and return
+/// the timings sorted from fastest to slowest.
+fn measure(warmup: usize, iterations: usize, mut f: impl FnMut()) -> Vec<Duration> {
+    for _ in 0..warmup {
+        f();
+    }
+
+    let mut samples: Vec<Duration> = (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .collect();
+    samples.sort();
+    samples
+}
+
+fn print_samples(name: &str, samples: Vec<Duration>) {
+    let median = samples[samples.len() / 2];
+    let min = samples[0];
+    let max = samples[samples.len() - 1];
+    println!(
+        "{name}\t{}\t{}\t{}",
+        median.as_nanos(),
+        min.as_nanos(),
+        max.as_nanos()
+    );
+}
@@ -0,0 +1,49 @@
+//! Derive-only scenario: links ks-types with the facet feature, but only
+//! uses the shapes reachable from `Catalog`.
+//!
+//! `ks_types::unused` derives `Facet` for a parallel type hierarchy that is
+//! never referenced. limpid scans this binary to check that none of those
+//! type names or shapes survive linking. Printing the shape tree of `Catalog`
+//! keeps the used shapes alive, so the scan also has something to find.
+
+use std::collections::BTreeSet;
+
+use facet::{Facet, Shape, Type, UserType};
+use ks_types::Catalog;
+
+fn main() {
+    let mut seen = BTreeSet::new();
+    print_shape(Catalog::SHAPE, 0, &mut seen);
+}
+
+/// Print a shape and, the first time it is seen, the shapes it is made of
+fn print_shape(shape: &'static Shape<'static>, depth: usize, seen: &mut BTreeSet<String>) {
+    let indent = "  ".repeat(depth);
+    let name = shape.to_string();
+    println!("{indent}{name}");
+    if !seen.insert(name) {
+        return;
+    }
+
+    for param in shape.type_params {
+        print_shape(param.shape(), depth + 1, seen);
+    }
+
+    match shape.ty {
+        Type::User(UserType::Struct(struct_type)) => {
+            for field in struct_type.fields {
+                println!("{indent}  .{}", field.name);
+                print_shape(field.shape, depth + 2, seen);
+            }
+        }
+        Type::User(UserType::Enum(enum_type)) => {
+            for variant in enum_type.variants {
+                println!("{indent}  ::{}", variant.name);
+                for field in variant.data.fields {
+                    print_shape(field.shape, depth + 2, seen);
+                }
+            }
+        }
+        _ => {}
+    }
+}
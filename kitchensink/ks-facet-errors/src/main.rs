@@ -0,0 +1,16 @@
+//! Feeds malformed catalogs to facet-json and prints the rendered errors, see
+//! `ks_mock::malformed::print_outcome` for the output format.
+
+use ks_mock::malformed::{SMALL_CATALOG_JSON, malformed_catalog_corpus, print_outcome};
+
+fn main() {
+    // Errors must come from the edits, not from the document they're made in
+    if let Err(e) = ks_facet_json_read::try_catalog_from_json(SMALL_CATALOG_JSON) {
+        panic!("the unedited small catalog doesn't parse: {e}");
+    }
+
+    for case in malformed_catalog_corpus() {
+        let outcome = ks_facet_json_read::try_catalog_from_json(&case.json).map(|_| ());
+        print_outcome(&case, outcome);
+    }
+}
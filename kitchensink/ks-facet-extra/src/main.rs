@@ -0,0 +1,15 @@
+//! Round-trips the mock marketplace (`ks_types::extra`) through facet-json.
+//!
+//! The second type set uses shapes facet-json doesn't fully handle yet, so this
+//! scenario is only built and measured, never run by limpid.
+
+fn main() {
+    let marketplace = ks_mock::generate_mock_marketplace();
+
+    // Serialize the marketplace to JSON
+    let serialized = ks_facet_json_write::marketplace_to_json(&marketplace);
+    eprintln!("Serialized marketplace JSON:\n{}", &serialized);
+
+    let deserialized = ks_facet_json_read::marketplace_from_json(&serialized);
+    ks_facet_pretty::pretty_print_marketplace(&deserialized);
+}
@@ -1,5 +1,15 @@
 use ks_types::Catalog;
+use ks_types::extra::Marketplace;
 
 pub fn catalog_from_json(json: &str) -> Catalog {
     facet_json::from_str(json).unwrap()
 }
+
+/// Like `catalog_from_json`, but returns the rendered error instead of panicking
+pub fn try_catalog_from_json(json: &str) -> Result<Catalog, String> {
+    facet_json::from_str(json).map_err(|e| e.to_string())
+}
+
+pub fn marketplace_from_json(json: &str) -> Marketplace {
+    facet_json::from_str(json).unwrap()
+}
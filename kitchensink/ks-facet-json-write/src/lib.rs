@@ -1,5 +1,10 @@
 use ks_types::Catalog;
+use ks_types::extra::Marketplace;
 
 pub fn catalog_to_json(catalog: &Catalog) -> String {
     facet_json::to_string(catalog)
 }
+
+pub fn marketplace_to_json(marketplace: &Marketplace) -> String {
+    facet_json::to_string(marketplace)
+}
@@ -1,6 +1,16 @@
 use facet_pretty::FacetPretty;
 use ks_types::Catalog;
+use ks_types::config::StoreConfig;
+use ks_types::extra::Marketplace;
 
 pub fn pretty_print(catalog: &Catalog) {
     eprintln!("{}", catalog.pretty());
 }
+
+pub fn pretty_print_marketplace(marketplace: &Marketplace) {
+    eprintln!("{}", marketplace.pretty());
+}
+
+pub fn pretty_print_store_config(config: &StoreConfig) {
+    eprintln!("{}", config.pretty());
+}
@@ -0,0 +1,160 @@
+//! Reflection-only scenario: no format crate involved.
+//!
+//! Walks the mock catalog with `Peek` and rebuilds a copy of it with
+//! `Partial` — struct field iteration, enum variant selection, lists,
+//! options and smart pointers — so the cost of the reflection core can be
+//! told apart from the cost of facet-json, facet-toml and friends.
+
+use facet::{Def, HasFields, Partial, Peek, ReflectError, Type, UserType};
+use ks_types::{Catalog, NaiveDate, NaiveDateTime, Uuid};
+
+/// How many values of each kind were visited
+#[derive(Debug, Default)]
+struct WalkStats {
+    structs: usize,
+    enums: usize,
+    lists: usize,
+    list_items: usize,
+    options: usize,
+    pointers: usize,
+    scalars: usize,
+}
+
+fn main() {
+    let catalog = ks_mock::generate_mock_catalog();
+
+    let mut stats = WalkStats::default();
+    let copy = reflect_clone(&catalog, &mut stats)
+        .unwrap_or_else(|e| panic!("Failed to copy catalog through reflection: {e}"));
+    eprintln!("Reflected over catalog: {stats:?}");
+
+    // Compare through the derived `Debug` impls, which don't involve facet
+    assert_eq!(
+        format!("{catalog:?}"),
+        format!("{copy:?}"),
+        "reflective copy differs from the original catalog"
+    );
+}
+
+/// Deep-copy a catalog by reading it through `Peek` and writing it through `Partial`
+fn reflect_clone(
+    catalog: &Catalog,
+    stats: &mut WalkStats,
+) -> Result<Catalog, ReflectError<'static>> {
+    let mut partial = Partial::alloc::<Catalog>()?;
+    copy_value(Peek::new(catalog), partial.inner_mut(), stats)?;
+    Ok(*partial.build()?)
+}
+
+/// Copy the value behind `peek` into the current frame of `partial`
+fn copy_value(
+    peek: Peek<'_, '_, 'static>,
+    partial: &mut Partial<'_, 'static>,
+    stats: &mut WalkStats,
+) -> Result<(), ReflectError<'static>> {
+    let shape = peek.shape();
+
+    // Leaves are cloned as-is: there is nothing to reflect over inside them
+    macro_rules! copy_leaf {
+        ($($ty:ty),*) => {
+            $(
+                if shape.is_type::<$ty>() {
+                    partial.set(peek.get::<$ty>()?.clone())?;
+                    stats.scalars += 1;
+                    return Ok(());
+                }
+            )*
+        };
+    }
+    copy_leaf!(
+        String,
+        Uuid,
+        NaiveDateTime,
+        NaiveDate,
+        bool,
+        u8,
+        u32,
+        u64,
+        f32,
+        f64
+    );
+
+    match shape.def {
+        Def::Option(_) => {
+            stats.options += 1;
+            match peek.into_option()?.value() {
+                Some(inner) => {
+                    partial.begin_some()?;
+                    copy_value(inner, partial, stats)?;
+                    partial.end()?;
+                }
+                None => {
+                    partial.set_default()?;
+                }
+            }
+            return Ok(());
+        }
+        Def::List(_) => {
+            stats.lists += 1;
+            partial.begin_list()?;
+            for item in peek.into_list_like()?.iter() {
+                stats.list_items += 1;
+                partial.begin_list_item()?;
+                copy_value(item, partial, stats)?;
+                partial.end()?;
+            }
+            return Ok(());
+        }
+        Def::SmartPointer(_) => {
+            stats.pointers += 1;
+            let inner =
+                peek.into_smart_pointer()?
+                    .borrow_inner()
+                    .ok_or(ReflectError::OperationFailed {
+                        shape,
+                        operation: "smart pointer cannot be borrowed",
+                    })?;
+            partial.begin_smart_ptr()?;
+            copy_value(inner, partial, stats)?;
+            partial.end()?;
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    match shape.ty {
+        Type::User(UserType::Struct(_)) => {
+            stats.structs += 1;
+            for (field, value) in peek.into_struct()?.fields() {
+                partial.begin_field(field.name)?;
+                copy_value(value, partial, stats)?;
+                partial.end()?;
+            }
+        }
+        Type::User(UserType::Enum(_)) => {
+            stats.enums += 1;
+            let peek_enum = peek.into_enum()?;
+            let variant =
+                peek_enum
+                    .variant_name_active()
+                    .map_err(|_| ReflectError::OperationFailed {
+                        shape,
+                        operation: "enum has no active variant",
+                    })?;
+            partial.select_variant_named(variant)?;
+            for (index, (_field, value)) in peek_enum.fields().enumerate() {
+                partial.begin_nth_enum_field(index)?;
+                copy_value(value, partial, stats)?;
+                partial.end()?;
+            }
+        }
+        _ => {
+            return Err(ReflectError::OperationFailed {
+                shape,
+                operation: "unsupported shape in reflective copy",
+            });
+        }
+    }
+
+    Ok(())
+}
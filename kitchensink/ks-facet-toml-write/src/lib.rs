@@ -0,0 +1,5 @@
+use ks_types::config::StoreConfig;
+
+pub fn config_to_toml(config: &StoreConfig) -> String {
+    facet_toml::to_string(config).unwrap()
+}
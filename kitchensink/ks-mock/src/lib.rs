@@ -4,50 +4,149 @@
 
 use ks_types::*;
 
-pub fn generate_mock_catalog() -> Catalog {
-    // Helper for now
-    fn now() -> NaiveDateTime {
-        chrono::Utc::now().naive_utc()
+pub mod malformed;
+
+/// Size and seed of the generated catalog.
+///
+/// The same configuration always produces the same catalog, down to the ids
+/// and timestamps, so serialized output is byte-identical across runs.
+#[derive(Clone, Debug)]
+pub struct MockConfig {
+    /// Seed of the pseudo-random generator used for ids, timestamps and amounts.
+    pub seed: u64,
+    /// Number of businesses in the catalog.
+    pub businesses: usize,
+    /// Number of users (employees) per business.
+    pub users: usize,
+    /// Number of branches per business, each stocking every product.
+    pub branches: usize,
+    /// Number of products per business.
+    pub products: usize,
+    /// Number of reviews per product.
+    pub reviews: usize,
+    /// Deepest category chain; products cycle through depths 1 to this.
+    pub category_depth: usize,
+}
+
+impl Default for MockConfig {
+    /// The historical mock shape: one business with 2 users, 1 branch and 3
+    /// products.
+    fn default() -> Self {
+        Self {
+            seed: 0x5eed,
+            businesses: 1,
+            users: 2,
+            branches: 1,
+            products: 3,
+            reviews: 1,
+            category_depth: 3,
+        }
+    }
+}
+
+impl MockConfig {
+    /// A catalog roughly `scale` times bigger than the default one.
+    pub fn scaled(scale: usize) -> Self {
+        let scale = scale.max(1);
+        Self {
+            businesses: scale,
+            users: 2 * scale,
+            products: 3 * scale,
+            ..Self::default()
+        }
+    }
+}
+
+/// SplitMix64: tiny, dependency-free, and stable across platforms and
+/// releases, unlike generators whose output may change between versions.
+struct MockRng(u64);
+
+impl MockRng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A value in `0..bound`.
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    fn uuid(&mut self) -> Uuid {
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&self.next_u64().to_le_bytes());
+        bytes[8..].copy_from_slice(&self.next_u64().to_le_bytes());
+        uuid::Builder::from_random_bytes(bytes).into_uuid()
+    }
+
+    /// A timestamp in 2024, with whole seconds so it round-trips through every
+    /// format unchanged.
+    fn timestamp(&mut self) -> NaiveDateTime {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        start + chrono::Duration::seconds(self.below(366 * 24 * 3600) as i64)
     }
-    fn today() -> NaiveDate {
-        chrono::Utc::now().date_naive()
+
+    /// A date between 1950 and 2005.
+    fn date_of_birth(&mut self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(1950, 1, 1).unwrap()
+            + chrono::Duration::days(self.below(55 * 365) as i64)
     }
-    fn mock_address() -> Address {
+}
+
+/// Generate the default mock catalog, see [`MockConfig::default`].
+pub fn generate_mock_catalog() -> Catalog {
+    generate_catalog(&MockConfig::default())
+}
+
+/// Generate a catalog of the given size, deterministically from `config.seed`.
+pub fn generate_catalog(config: &MockConfig) -> Catalog {
+    let mut rng = MockRng(config.seed);
+    let rng = &mut rng;
+
+    fn mock_address(idx: usize) -> Address {
+        // Owners are numbered from 1_000_000, keep their addresses plausible
+        let idx = idx % 1000;
         Address {
-            street: "123 Main St.".to_string(),
+            street: format!("{} Main St.", 100 + idx),
             city: "Metropolis".to_string(),
             state: "Stateville".to_string(),
-            postal_code: "12345".to_string(),
+            postal_code: format!("{:05}", 12345 + idx),
             country: "Countryland".to_string(),
             geo: Some(GeoLocation {
-                latitude: 51.0,
+                latitude: 51.0 + idx as f64 / 1000.0,
                 longitude: -0.1,
             }),
         }
     }
-    fn mock_role() -> Role {
+    fn mock_role(rng: &mut MockRng) -> Role {
         Role {
-            id: Uuid::new_v4(),
+            id: rng.uuid(),
             name: "Employee".to_string(),
             description: Some("Regular employee role".to_string()),
             permissions: vec![Permission {
-                id: Uuid::new_v4(),
+                id: rng.uuid(),
                 name: "access_dashboard".to_string(),
                 description: Some("Can access the dashboard".to_string()),
             }],
         }
     }
-    fn mock_user(idx: u32) -> User {
+    fn mock_user(rng: &mut MockRng, idx: usize) -> User {
         User {
-            id: Uuid::new_v4(),
+            id: rng.uuid(),
             username: format!("user{}", idx),
             email: format!("user{}@email.com", idx),
-            created_at: now(),
-            updated_at: now(),
+            created_at: rng.timestamp(),
+            updated_at: rng.timestamp(),
             profile: UserProfile {
                 first_name: format!("First{}", idx),
                 last_name: format!("Last{}", idx),
-                date_of_birth: today(),
+                date_of_birth: rng.date_of_birth(),
                 gender: if idx % 2 == 0 {
                     Gender::Male
                 } else {
@@ -55,10 +154,10 @@ pub fn generate_mock_catalog() -> Catalog {
                 },
                 bio: Some(format!("Bio of user {}", idx)),
                 avatar_url: None,
-                home_address: mock_address(),
+                home_address: mock_address(idx),
             },
             settings: Settings {
-                user_id: Uuid::new_v4(),
+                user_id: rng.uuid(),
                 email_notifications: true,
                 push_notifications: idx % 2 == 0,
                 theme: if idx % 2 == 0 {
@@ -70,114 +169,274 @@ pub fn generate_mock_catalog() -> Catalog {
             },
         }
     }
-    fn mock_user_summary(idx: u32) -> UserSummary {
+    fn mock_user_summary(rng: &mut MockRng, idx: usize) -> UserSummary {
         UserSummary {
-            id: Uuid::new_v4(),
+            id: rng.uuid(),
             username: format!("user{}", idx),
             avatar_url: None,
         }
     }
-    fn mock_category(id: u8) -> Category {
-        if id == 0 {
+    fn mock_category(rng: &mut MockRng, depth: usize) -> Category {
+        if depth == 0 {
             Category {
-                id: Uuid::new_v4(),
+                id: rng.uuid(),
                 name: "Root Category".to_string(),
                 description: Some("Top of the tree".to_string()),
                 parent: None,
             }
         } else {
             Category {
-                id: Uuid::new_v4(),
-                name: format!("Subcategory {}", id),
-                description: Some(format!("Subcategory number {}", id)),
-                parent: Some(Box::new(mock_category(id - 1))),
+                id: rng.uuid(),
+                name: format!("Subcategory {}", depth),
+                description: Some(format!("Subcategory number {}", depth)),
+                parent: Some(Box::new(mock_category(rng, depth - 1))),
             }
         }
     }
-    fn mock_product(idx: u32) -> Product {
+    fn mock_product(rng: &mut MockRng, config: &MockConfig, idx: usize) -> Product {
         Product {
-            id: Uuid::new_v4(),
+            id: rng.uuid(),
             name: format!("Product{}", idx),
             description: Some(format!("Description for product {}", idx)),
-            price_cents: (1000 + (idx * 100)) as u64,
+            price_cents: 100 + rng.below(100_000),
             currency: "USD".to_string(),
-            available: true,
+            available: rng.below(10) != 0,
             metadata: Some(ProductMetadata {
                 sku: Some(format!("SKU{}", idx)),
                 categories: vec!["Electronics".to_string(), "Gadgets".to_string()],
-                weight_grams: Some(500 + 10 * idx),
+                weight_grams: Some(100 + rng.below(5000) as u32),
                 dimensions: Some(ProductDimensions {
-                    length_mm: Some(100.0 + idx as f32),
-                    width_mm: Some(50.0 + idx as f32),
-                    height_mm: Some(25.5 + idx as f32),
+                    length_mm: Some(10.0 + rng.below(400) as f32 / 2.0),
+                    width_mm: Some(10.0 + rng.below(400) as f32 / 2.0),
+                    height_mm: Some(10.0 + rng.below(400) as f32 / 2.0),
                 }),
             }),
-            reviews: vec![ProductReview {
-                id: Uuid::new_v4(),
-                reviewer: mock_user_summary(idx),
-                rating: 4 + ((idx % 2) as u8),
-                text: Some(format!("Review for product {}", idx)),
-                created_at: now(),
-            }],
-            categories: vec![mock_category(idx as u8)],
+            reviews: (0..config.reviews)
+                .map(|r| ProductReview {
+                    id: rng.uuid(),
+                    reviewer: mock_user_summary(rng, r),
+                    rating: 1 + rng.below(5) as u8,
+                    text: Some(format!("Review {} for product {}", r, idx)),
+                    created_at: rng.timestamp(),
+                })
+                .collect(),
+            categories: match config.category_depth {
+                0 => vec![],
+                depth => vec![mock_category(rng, 1 + (idx - 1) % depth)],
+            },
         }
     }
 
-    // Construct mock business users
-    let business_users: Vec<BusinessUser> = (1..=2)
-        .map(|i| BusinessUser {
-            user: mock_user(i),
-            roles: vec![mock_role()],
-            is_active: true,
-            created_at: now(),
-        })
-        .collect();
+    let businesses = (1..=config.businesses)
+        .map(|b| {
+            // Users are numbered across businesses so usernames stay unique
+            let first_user = (b - 1) * config.users;
+            let business_users: Vec<BusinessUser> = (1..=config.users)
+                .map(|i| BusinessUser {
+                    user: mock_user(rng, first_user + i),
+                    roles: vec![mock_role(rng)],
+                    is_active: true,
+                    created_at: rng.timestamp(),
+                })
+                .collect();
+
+            let owner = BusinessOwner {
+                user: mock_user(rng, 1_000_000 + b),
+                ownership_percent: 100.0,
+            };
+
+            let first_product = (b - 1) * config.products;
+            let products: Vec<Product> = (1..=config.products)
+                .map(|i| mock_product(rng, config, first_product + i))
+                .collect();
 
-    // mock owner
-    let owner = BusinessOwner {
-        user: mock_user(100),
-        ownership_percent: 100.0,
-    };
-
-    // mock branch with inventory
-    let products: Vec<Product> = (1..=3).map(mock_product).collect();
-    let branch_inventory: Vec<BranchInventory> = products
-        .iter()
-        .cloned()
-        .map(|p| BranchInventory {
-            product: p,
-            stock: 50,
-            location_code: Some("A-01".to_string()),
+            let branches = (1..=config.branches)
+                .map(|br| Branch {
+                    id: rng.uuid(),
+                    name: format!("Branch {}", br),
+                    address: mock_address(br),
+                    employees: business_users.clone(),
+                    inventory: products
+                        .iter()
+                        .cloned()
+                        .map(|product| BranchInventory {
+                            product,
+                            stock: rng.below(200) as u32,
+                            location_code: Some(format!("A-{:02}", br)),
+                        })
+                        .collect(),
+                    open: true,
+                })
+                .collect();
+
+            Business {
+                id: rng.uuid(),
+                name: format!("Business {}", b),
+                address: mock_address(b),
+                owner,
+                users: business_users,
+                branches,
+                products,
+                created_at: rng.timestamp(),
+            }
         })
         .collect();
 
-    let branch = Branch {
-        id: Uuid::new_v4(),
-        name: "Central Branch".to_string(),
-        address: mock_address(),
-        employees: business_users.clone(),
-        inventory: branch_inventory,
-        open: true,
-    };
-
-    let business = Business {
-        id: Uuid::new_v4(),
-        name: "Awesome Business".to_string(),
-        address: mock_address(),
-        owner,
-        users: business_users,
-        branches: vec![branch],
-        products,
-        created_at: now(),
-    };
-
     Catalog {
-        id: Uuid::new_v4(),
-        businesses: vec![business],
-        created_at: now(),
+        id: rng.uuid(),
+        businesses,
+        created_at: rng.timestamp(),
         metadata: CatalogMetadata {
             version: "1.0.1!".to_string(),
             region: "US".to_string(),
         },
     }
 }
+
+/// Generate the mock store configuration, describing the first business of
+/// the default catalog.
+pub fn generate_mock_store_config() -> config::StoreConfig {
+    use config::*;
+
+    let catalog = generate_mock_catalog();
+    let business = &catalog.businesses[0];
+
+    StoreConfig {
+        name: business.name.clone(),
+        version: catalog.metadata.version.clone(),
+        region: catalog.metadata.region.clone(),
+        headquarters: business.address.clone(),
+        checkout: CheckoutConfig {
+            currency: "USD".to_string(),
+            tax_percent: 8.25,
+            free_shipping_above_cents: 5_000,
+            guest_checkout: true,
+        },
+        mail: Some(MailConfig {
+            host: "smtp.email.com".to_string(),
+            port: 587,
+            sender: "noreply@email.com".to_string(),
+            use_tls: true,
+        }),
+        branches: business
+            .branches
+            .iter()
+            .enumerate()
+            .map(|(idx, branch)| {
+                let config = BranchConfig {
+                    address: branch.address.clone(),
+                    open: branch.open,
+                    employees: branch.employees.len() as u32,
+                    phone: Some(format!("+1-555-01{:02}", idx + 1)),
+                };
+                (branch.name.clone(), config)
+            })
+            .collect(),
+        products: business
+            .products
+            .iter()
+            .map(|product| ProductConfig {
+                sku: product
+                    .metadata
+                    .as_ref()
+                    .and_then(|m| m.sku.clone())
+                    .unwrap_or_default(),
+                name: product.name.clone(),
+                price_cents: product.price_cents,
+                stock: business
+                    .branches
+                    .iter()
+                    .flat_map(|branch| &branch.inventory)
+                    .filter(|inventory| inventory.product.id == product.id)
+                    .map(|inventory| inventory.stock)
+                    .sum(),
+                available: product.available,
+            })
+            .collect(),
+    }
+}
+
+/// Seed of the marketplace ids and timestamps, distinct from the catalog's so
+/// the two don't share ids
+const MARKETPLACE_SEED: u64 = 0x6a7c;
+
+/// Generate the mock marketplace, with ids and timestamps derived from a fixed
+/// seed. `tag_counts` is a `HashMap`, so its serialized order still
+/// varies between runs.
+pub fn generate_mock_marketplace() -> extra::Marketplace {
+    use extra::*;
+    use std::collections::{BTreeMap, HashMap};
+    use std::sync::Arc;
+
+    let mut rng = MockRng(MARKETPLACE_SEED);
+    let now = rng.timestamp();
+    let region = Arc::new(MarketRegion {
+        code: "eu-west".to_string(),
+        name: "Western Europe".to_string(),
+    });
+
+    let listings: Vec<Listing> = (1..=3)
+        .map(|idx: u32| Listing {
+            id: rng.uuid(),
+            sku: Sku(format!("SKU{}", idx)),
+            pickup: Point(48.85 + idx as f64 / 100.0, 2.35),
+            photo_hash: [idx as u8; 8],
+            region: region.clone(),
+            boost: idx,
+            rendered_html: format!("<h1>Listing {}</h1>", idx),
+            audit: AuditStamp {
+                updated_by: rng.uuid(),
+                updated_at: now,
+                revision: idx,
+            },
+        })
+        .collect();
+
+    let events = vec![
+        ListingEvent::Published {
+            listing: listings[0].id,
+            at: now,
+        },
+        ListingEvent::Renamed("Old title".to_string(), "New title".to_string()),
+        ListingEvent::Repriced {
+            old_cents: 1200,
+            new_cents: 999,
+        },
+        ListingEvent::Moved(Point(45.76, 4.84)),
+        ListingEvent::Withdrawn,
+    ];
+
+    let tag_counts: HashMap<String, u32> = [("electronics", 2), ("gadgets", 1)]
+        .into_iter()
+        .map(|(tag, count)| (tag.to_string(), count))
+        .collect();
+
+    let locales: BTreeMap<String, LocaleSettings> = [("en", "GBP", false), ("fr", "EUR", true)]
+        .into_iter()
+        .map(|(lang, currency, prices_include_tax)| {
+            (
+                lang.to_string(),
+                LocaleSettings {
+                    currency: currency.to_string(),
+                    prices_include_tax,
+                },
+            )
+        })
+        .collect();
+
+    Marketplace {
+        listings,
+        events,
+        daily_sales: Series {
+            name: "daily sales".to_string(),
+            values: vec![12, 7, 31],
+        },
+        daily_prices: Series {
+            name: "daily average price".to_string(),
+            values: vec![999.0, 1049.5, 1012.25],
+        },
+        tag_counts,
+        locales,
+        region,
+    }
+}
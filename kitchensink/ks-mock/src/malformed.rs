@@ -0,0 +1,182 @@
+//! Malformed and mistyped JSON documents, to exercise the error paths of the
+//! JSON readers.
+//!
+//! Every case is a single edit of [`SMALL_CATALOG_JSON`], so the rest of the
+//! document is valid and the error can only come from that edit.
+
+/// A small but valid catalog (one business, branch, product, review and a
+/// two-level category), pretty-printed so errors have meaningful line numbers.
+pub const SMALL_CATALOG_JSON: &str = include_str!("small_catalog.json");
+
+/// A JSON document that must not deserialize into a `Catalog`.
+pub struct MalformedCase {
+    /// Short, stable identifier (e.g. `wrong-type`).
+    pub name: &'static str,
+    /// What was broken, for humans.
+    pub description: &'static str,
+    /// Field name or value a helpful error message mentions, if any.
+    pub culprit: Option<&'static str>,
+    pub json: String,
+}
+
+/// Replace the single occurrence of `from` in the small catalog.
+fn edit(from: &str, to: &str) -> String {
+    assert_eq!(
+        SMALL_CATALOG_JSON.matches(from).count(),
+        1,
+        "`{from}` must occur exactly once in small_catalog.json"
+    );
+    SMALL_CATALOG_JSON.replacen(from, to, 1)
+}
+
+/// The malformed catalogs, syntax errors first, then well-formed JSON that
+/// doesn't match the types.
+pub fn malformed_catalog_corpus() -> Vec<MalformedCase> {
+    let case = |name, description, culprit, json| MalformedCase {
+        name,
+        description,
+        culprit,
+        json,
+    };
+
+    vec![
+        case("empty", "empty input", None, String::new()),
+        case(
+            "truncated",
+            "document cut in the middle",
+            None,
+            SMALL_CATALOG_JSON[..SMALL_CATALOG_JSON.len() / 2].to_string(),
+        ),
+        case(
+            "trailing-comma",
+            "comma after the last field of an object",
+            None,
+            edit(r#""region": "US""#, r#""region": "US","#),
+        ),
+        case(
+            "unterminated-string",
+            "string missing its closing quote",
+            None,
+            edit(r#""name": "Product1","#, r#""name": "Product1,"#),
+        ),
+        case(
+            "invalid-escape",
+            "unknown escape sequence in a string",
+            None,
+            edit("Description for product 1", r"Description for \q product 1"),
+        ),
+        case(
+            "trailing-characters",
+            "extra closing brace after the document",
+            None,
+            format!("{SMALL_CATALOG_JSON}}}"),
+        ),
+        case(
+            "root-array",
+            "array instead of an object",
+            None,
+            "[]".to_string(),
+        ),
+        case(
+            "wrong-type",
+            "number given as a string",
+            Some("price_cents"),
+            edit(r#""price_cents": 62328"#, r#""price_cents": "62328""#),
+        ),
+        case(
+            "out-of-range",
+            "`u8` field set to 300",
+            Some("rating"),
+            edit(r#""rating": 1"#, r#""rating": 300"#),
+        ),
+        case(
+            "negative-unsigned",
+            "`u32` field set to a negative number",
+            Some("weight_grams"),
+            edit(r#""weight_grams": 1895"#, r#""weight_grams": -5"#),
+        ),
+        case(
+            "missing-field",
+            "required field removed",
+            Some("currency"),
+            edit("\n          \"currency\": \"USD\",", ""),
+        ),
+        case(
+            "null-field",
+            "`null` for a non-optional field",
+            Some("name"),
+            edit(r#""name": "Branch 1""#, r#""name": null"#),
+        ),
+        case(
+            "wrong-container",
+            "object instead of an array",
+            Some("employees"),
+            edit(r#""employees": []"#, r#""employees": {}"#),
+        ),
+        case(
+            "unknown-variant",
+            "enum variant that doesn't exist",
+            Some("Robot"),
+            edit(r#""gender": "Female""#, r#""gender": "Robot""#),
+        ),
+        case(
+            "invalid-uuid",
+            "malformed UUID",
+            Some("not-a-uuid"),
+            edit(
+                r#""id": "2dd4c913-c478-4c27-8ec9-89b23868de4d""#,
+                r#""id": "not-a-uuid""#,
+            ),
+        ),
+        case(
+            "invalid-datetime",
+            "month 13 in a timestamp",
+            Some("2024-13-16T18:08:09"),
+            edit(
+                r#""created_at": "2024-01-16T18:08:09""#,
+                r#""created_at": "2024-13-16T18:08:09""#,
+            ),
+        ),
+    ]
+}
+
+/// Print the outcome of reading a case, in the tab-separated format limpid
+/// parses. ANSI colors are stripped from the message, and its tabs, newlines
+/// and backslashes escaped.
+///
+/// ```text
+/// case  <name>  <culprit or ->  rejected  <message>
+/// case  <name>  <culprit or ->  accepted
+/// ```
+pub fn print_outcome(case: &MalformedCase, outcome: Result<(), String>) {
+    let culprit = case.culprit.unwrap_or("-");
+    match outcome {
+        Ok(()) => println!("case\t{}\t{}\taccepted", case.name, culprit),
+        Err(message) => {
+            let escaped = strip_ansi(&message)
+                .replace('\\', "\\\\")
+                .replace('\t', "\\t")
+                .replace('\n', "\\n");
+            println!("case\t{}\t{}\trejected\t{}", case.name, culprit, escaped);
+        }
+    }
+}
+
+/// Remove ANSI escape sequences such as `\x1b[1;31m`, which colored error
+/// messages are full of
+pub fn strip_ansi(s: &str) -> String {
+    let mut plain = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            plain.push(c);
+        }
+    }
+    plain
+}
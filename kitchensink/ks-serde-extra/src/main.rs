@@ -0,0 +1,13 @@
+//! Round-trips the mock marketplace (`ks_types::extra`) through serde_json,
+//! the serde counterpart of `ks-facet-extra`.
+
+fn main() {
+    let marketplace = ks_mock::generate_mock_marketplace();
+
+    // Serialize the marketplace to JSON
+    let serialized = ks_serde_json_write::marketplace_to_json(&marketplace);
+    eprintln!("Serialized marketplace JSON:\n{}", &serialized);
+
+    let deserialized = ks_serde_json_read::marketplace_from_json(&serialized);
+    ks_debug::pretty_print_marketplace(&deserialized);
+}
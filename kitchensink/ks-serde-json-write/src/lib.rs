@@ -1,5 +1,10 @@
 use ks_types::Catalog;
+use ks_types::extra::Marketplace;
 
 pub fn catalog_to_json(catalog: &Catalog) -> String {
     serde_json::to_string(catalog).unwrap()
 }
+
+pub fn marketplace_to_json(marketplace: &Marketplace) -> String {
+    serde_json::to_string(marketplace).unwrap()
+}
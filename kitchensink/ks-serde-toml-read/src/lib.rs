@@ -0,0 +1,5 @@
+use ks_types::config::StoreConfig;
+
+pub fn config_from_toml(toml: &str) -> StoreConfig {
+    toml::from_str(toml).unwrap()
+}
@@ -0,0 +1,10 @@
+fn main() {
+    let config = ks_mock::generate_mock_store_config();
+
+    // Serialize the store configuration to TOML
+    let serialized = ks_serde_toml_write::config_to_toml(&config);
+    eprintln!("Serialized store configuration TOML:\n{}", &serialized);
+
+    let deserialized = ks_serde_toml_read::config_from_toml(&serialized);
+    ks_debug::pretty_print_store_config(&deserialized);
+}
@@ -1,10 +1,27 @@
+#[cfg(feature = "count-allocs")]
+use ks_alloc::measure;
+
+#[cfg(feature = "count-allocs")]
+#[global_allocator]
+static ALLOCATOR: ks_alloc::CountingAllocator = ks_alloc::CountingAllocator;
+
 fn main() {
     let catalog = ks_mock::generate_mock_catalog();
 
     // Serialize the catalog to JSON
-    let serialized = ks_serde_json_write::catalog_to_json(&catalog);
+    let serialized = measure("serialize", || {
+        ks_serde_json_write::catalog_to_json(&catalog)
+    });
     eprintln!("Serialized catalog JSON:\n{}", &serialized);
 
-    let deserialized = ks_serde_json_read::catalog_from_json(&serialized);
+    let deserialized = measure("deserialize", || {
+        ks_serde_json_read::catalog_from_json(&serialized)
+    });
     ks_debug::pretty_print(&deserialized);
 }
+
+/// Without the `count-allocs` feature, run the closure as-is
+#[cfg(not(feature = "count-allocs"))]
+fn measure<T>(_label: &str, f: impl FnOnce() -> T) -> T {
+    f()
+}
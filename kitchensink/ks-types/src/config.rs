@@ -0,0 +1,101 @@
+//! A store configuration file, the kind of document TOML is used for.
+//!
+//! Unlike `Catalog`, it sticks to what facet-toml round-trips today: strings,
+//! numbers and booleans, nested and optional tables, maps of tables, and arrays
+//! of tables whose fields are always present. No ids, timestamps or enums.
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "facet")]
+use facet::Facet;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Address;
+
+/// Configuration of a store and its branches.
+///
+/// The root of the document, written and read back by the TOML scenarios.
+#[cfg_attr(feature = "facet", derive(Facet))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct StoreConfig {
+    /// Display name of the store.
+    pub name: String,
+    /// Version of the configuration format.
+    pub version: String,
+    /// Regional code of the deployment (e.g. 'us-east').
+    pub region: String,
+    /// Address of the store headquarters.
+    pub headquarters: Address,
+    /// Pricing and checkout settings shared by every branch.
+    pub checkout: CheckoutConfig,
+    /// Outgoing mail server, if the store sends notifications.
+    pub mail: Option<MailConfig>,
+    /// Branches of the store, keyed by name.
+    pub branches: BTreeMap<String, BranchConfig>,
+    /// Products offered at every branch.
+    pub products: Vec<ProductConfig>,
+}
+
+/// Pricing and checkout settings.
+#[cfg_attr(feature = "facet", derive(Facet))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct CheckoutConfig {
+    /// ISO 4217 currency code (e.g. "USD", "EUR").
+    pub currency: String,
+    /// Sales tax added to every order, in percent.
+    pub tax_percent: f32,
+    /// Orders above this amount (smallest currency unit) ship for free.
+    pub free_shipping_above_cents: u64,
+    /// Whether customers may order without an account.
+    pub guest_checkout: bool,
+}
+
+/// Settings of the outgoing mail server.
+#[cfg_attr(feature = "facet", derive(Facet))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct MailConfig {
+    /// Host name of the SMTP server.
+    pub host: String,
+    /// Port of the SMTP server.
+    pub port: u16,
+    /// Address notifications are sent from.
+    pub sender: String,
+    /// Whether to connect with TLS.
+    pub use_tls: bool,
+}
+
+/// Configuration of one branch.
+#[cfg_attr(feature = "facet", derive(Facet))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct BranchConfig {
+    /// Physical address of the branch.
+    pub address: Address,
+    /// Whether the branch is open for business.
+    pub open: bool,
+    /// Number of employees working at the branch.
+    pub employees: u32,
+    /// Optional phone number of the branch.
+    pub phone: Option<String>,
+}
+
+/// A product offered by the store.
+#[cfg_attr(feature = "facet", derive(Facet))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct ProductConfig {
+    /// Stock keeping unit.
+    pub sku: String,
+    /// Human-readable product name.
+    pub name: String,
+    /// Retail price in the smallest currency unit (e.g., cents).
+    pub price_cents: u64,
+    /// Units in stock across all branches.
+    pub stock: u32,
+    /// Whether the product is currently available for sale.
+    pub available: bool,
+}
@@ -0,0 +1,161 @@
+//! A second type set, covering the type shapes `Catalog` doesn't use: enums with
+//! tuple and struct variants, generic and tuple structs, maps, arrays, `Arc`,
+//! transparent newtypes, and the field attributes shared by facet and serde.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+#[cfg(feature = "facet")]
+use facet::Facet;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Marketplace data attached to a catalog.
+///
+/// Groups the other types of this module so a single value exercises all of them.
+#[cfg_attr(feature = "facet", derive(Facet))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct Marketplace {
+    /// Listings currently published on the marketplace.
+    pub listings: Vec<Listing>,
+    /// Chronological log of changes made to the listings.
+    pub events: Vec<ListingEvent>,
+    /// Daily sales volume, in units sold.
+    pub daily_sales: Series<u32>,
+    /// Daily average price, in the smallest currency unit.
+    pub daily_prices: Series<f64>,
+    /// Number of listings per tag.
+    pub tag_counts: HashMap<String, u32>,
+    /// Localized settings, keyed by ISO 639-1 language code.
+    pub locales: BTreeMap<String, LocaleSettings>,
+    /// Region served by the marketplace, shared with every listing.
+    pub region: Arc<MarketRegion>,
+}
+
+/// A product offered for sale on the marketplace.
+#[cfg_attr(feature = "facet", derive(Facet))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct Listing {
+    /// Unique identifier of the listing, serialized as `listingId`.
+    #[cfg_attr(feature = "facet", facet(rename = "listingId"))]
+    #[cfg_attr(feature = "serde", serde(rename = "listingId"))]
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
+    pub id: Uuid,
+    /// Stock keeping unit of the listed product.
+    pub sku: Sku,
+    /// Pickup point of the listing.
+    pub pickup: Point,
+    /// Fingerprint of the listing photos.
+    pub photo_hash: [u8; 8],
+    /// Region the listing is published in.
+    pub region: Arc<MarketRegion>,
+    /// Search ranking boost, defaulted when missing from the input.
+    #[cfg_attr(feature = "facet", facet(default))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub boost: u32,
+    /// Rendered listing page, only kept in memory.
+    #[cfg_attr(feature = "facet", facet(skip_serializing, default))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub rendered_html: String,
+    /// Audit fields, serialized inline with the listing's own fields.
+    #[cfg_attr(feature = "facet", facet(flatten))]
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub audit: AuditStamp,
+}
+
+/// Who last changed a record, and when.
+#[cfg_attr(feature = "facet", derive(Facet))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct AuditStamp {
+    /// User who made the last change.
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
+    pub updated_by: Uuid,
+    /// Time of the last change.
+    #[cfg_attr(all(feature = "facet", not(feature = "chrono")), facet(opaque))]
+    pub updated_at: NaiveDateTime,
+    /// Number of changes made so far.
+    pub revision: u32,
+}
+
+/// A change made to a listing.
+#[cfg_attr(feature = "facet", derive(Facet))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+#[repr(u8)]
+pub enum ListingEvent {
+    /// The listing was published.
+    Published {
+        /// Listing that was published.
+        #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
+        listing: Uuid,
+        /// Time of publication.
+        #[cfg_attr(all(feature = "facet", not(feature = "chrono")), facet(opaque))]
+        at: NaiveDateTime,
+    },
+    /// The listing title changed from the first to the second value.
+    Renamed(String, String),
+    /// The listing price changed.
+    Repriced {
+        /// Previous price, in the smallest currency unit.
+        old_cents: u64,
+        /// New price, in the smallest currency unit.
+        new_cents: u64,
+    },
+    /// The listing was moved to another pickup point.
+    Moved(Point),
+    /// The listing was withdrawn.
+    Withdrawn,
+}
+
+/// A named series of values sampled at a fixed interval.
+#[cfg_attr(feature = "facet", derive(Facet))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct Series<T> {
+    /// Name of the series (e.g. "daily sales").
+    pub name: String,
+    /// Sampled values, oldest first.
+    pub values: Vec<T>,
+}
+
+/// A latitude/longitude pair in decimal degrees.
+#[cfg_attr(feature = "facet", derive(Facet))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct Point(pub f64, pub f64);
+
+/// Stock keeping unit, serialized as a plain string.
+#[cfg_attr(feature = "facet", derive(Facet))]
+#[cfg_attr(feature = "facet", facet(transparent))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[derive(Clone, Debug)]
+pub struct Sku(pub String);
+
+/// Per-language marketplace settings.
+#[cfg_attr(feature = "facet", derive(Facet))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct LocaleSettings {
+    /// ISO 4217 currency code prices are shown in.
+    pub currency: String,
+    /// Whether prices are shown with taxes included.
+    pub prices_include_tax: bool,
+}
+
+/// Geographical area a marketplace serves.
+#[cfg_attr(feature = "facet", derive(Facet))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct MarketRegion {
+    /// Region code (e.g. "eu-west").
+    pub code: String,
+    /// Display name of the region.
+    pub name: String,
+}
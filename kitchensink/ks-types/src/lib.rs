@@ -6,6 +6,8 @@ use facet::Facet;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+pub mod config;
+pub mod extra;
 pub mod unused;
 
 /// The root struct representing the catalog of everything.
@@ -18,10 +20,12 @@ pub mod unused;
 pub struct Catalog {
     /// Catalog unique identifier.
     /// Automatically generated as a UUID to prevent collisions.
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
     pub id: Uuid,
     /// List of all businesses included in the catalog.
     pub businesses: Vec<Business>,
     /// Timestamp at which this catalog instance was created.
+    #[cfg_attr(all(feature = "facet", not(feature = "chrono")), facet(opaque))]
     pub created_at: NaiveDateTime,
     /// Metadata providing additional information about the catalog such as version.
     pub metadata: CatalogMetadata,
@@ -49,6 +53,7 @@ pub struct CatalogMetadata {
 #[derive(Clone, Debug)]
 pub struct Business {
     /// Unique business identifier.
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
     pub id: Uuid,
     /// Display name of the business (e.g. "Joe's Cafe").
     pub name: String,
@@ -63,6 +68,7 @@ pub struct Business {
     /// The catalog of products sold or managed by the business.
     pub products: Vec<Product>,
     /// Timestamp marking when the business account was created.
+    #[cfg_attr(all(feature = "facet", not(feature = "chrono")), facet(opaque))]
     pub created_at: NaiveDateTime,
 }
 
@@ -87,6 +93,7 @@ pub struct BusinessOwner {
 #[derive(Clone, Debug)]
 pub struct Branch {
     /// Unique branch identifier.
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
     pub id: Uuid,
     /// Name of the branch (e.g. "Downtown", "Online").
     pub name: String,
@@ -129,6 +136,7 @@ pub struct BusinessUser {
     /// Whether this user is currently considered active in the business context.
     pub is_active: bool,
     /// Timestamp recording when this user account was created or joined.
+    #[cfg_attr(all(feature = "facet", not(feature = "chrono")), facet(opaque))]
     pub created_at: NaiveDateTime,
 }
 
@@ -140,14 +148,17 @@ pub struct BusinessUser {
 #[derive(Clone, Debug)]
 pub struct User {
     /// Globally unique identifier for the user account.
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
     pub id: Uuid,
     /// The username chosen or assigned for this user (must be unique).
     pub username: String,
     /// The user's email address (used for notifications and login).
     pub email: String,
     /// When this user account was created in the system.
+    #[cfg_attr(all(feature = "facet", not(feature = "chrono")), facet(opaque))]
     pub created_at: NaiveDateTime,
     /// Last time the user's profile or account information was updated.
+    #[cfg_attr(all(feature = "facet", not(feature = "chrono")), facet(opaque))]
     pub updated_at: NaiveDateTime,
     /// Extended profile information (personal and contact data).
     pub profile: UserProfile,
@@ -167,6 +178,7 @@ pub struct UserProfile {
     /// Surname or last/family name.
     pub last_name: String,
     /// Date of birth for the user (used for age calculations, eligibility, etc.).
+    #[cfg_attr(all(feature = "facet", not(feature = "chrono")), facet(opaque))]
     pub date_of_birth: NaiveDate,
     /// Self-specified gender identity.
     pub gender: Gender,
@@ -238,6 +250,7 @@ pub enum Gender {
 #[derive(Clone, Debug)]
 pub struct Product {
     /// SKU or catalog-wide unique identifier for the product.
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
     pub id: Uuid,
     /// Human-readable product name.
     pub name: String,
@@ -297,6 +310,7 @@ pub struct ProductDimensions {
 #[derive(Clone, Debug)]
 pub struct ProductReview {
     /// Unique identifier for the review.
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
     pub id: Uuid,
     /// Minimal user information for the review author.
     pub reviewer: UserSummary,
@@ -305,6 +319,7 @@ pub struct ProductReview {
     /// Optional review text or comment body.
     pub text: Option<String>,
     /// Date and time when this review was created.
+    #[cfg_attr(all(feature = "facet", not(feature = "chrono")), facet(opaque))]
     pub created_at: NaiveDateTime,
 }
 
@@ -316,6 +331,7 @@ pub struct ProductReview {
 #[derive(Clone, Debug)]
 pub struct Category {
     /// Unique identifier for the category node.
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
     pub id: Uuid,
     /// Display label for this category.
     pub name: String,
@@ -333,6 +349,7 @@ pub struct Category {
 #[derive(Clone, Debug)]
 pub struct UserSummary {
     /// User's UUID.
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
     pub id: Uuid,
     /// User's public handle/username.
     pub username: String,
@@ -348,6 +365,7 @@ pub struct UserSummary {
 #[derive(Clone, Debug)]
 pub struct Role {
     /// Unique role identifier.
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
     pub id: Uuid,
     /// Human-readable name of role (must be unique per business).
     pub name: String,
@@ -365,6 +383,7 @@ pub struct Role {
 #[derive(Clone, Debug)]
 pub struct Permission {
     /// Identifier for the specific permission action.
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
     pub id: Uuid,
     /// Text label of the permission (e.g. "edit_products").
     pub name: String,
@@ -380,6 +399,7 @@ pub struct Permission {
 #[derive(Clone, Debug)]
 pub struct Settings {
     /// Reference back to the user this settings profile belongs to.
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
     pub user_id: Uuid,
     /// Controls whether user will receive emails.
     pub email_notifications: bool,
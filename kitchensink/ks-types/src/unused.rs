@@ -15,10 +15,12 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug)]
 pub struct Inventory {
     /// Universally unique identifier for this inventory snapshot.
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
     pub id: Uuid,
     /// List of companies managed within this inventory.
     pub companies: Vec<Company>,
     /// Date and time this inventory was created.
+    #[cfg_attr(all(feature = "facet", not(feature = "chrono")), facet(opaque))]
     pub created_at: NaiveDateTime,
     /// Arbitrary metadata about this inventory file.
     pub metadata: InventoryMetadata,
@@ -43,6 +45,7 @@ pub struct InventoryMetadata {
 #[derive(Clone, Debug)]
 pub struct Company {
     /// The unique company UUID.
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
     pub id: Uuid,
     /// The company's formal or legal name.
     pub name: String,
@@ -57,6 +60,7 @@ pub struct Company {
     /// The products or services this company sells.
     pub goods: Vec<Item>,
     /// Timestamp for when the company record was created in the system.
+    #[cfg_attr(all(feature = "facet", not(feature = "chrono")), facet(opaque))]
     pub created_at: NaiveDateTime,
 }
 
@@ -81,6 +85,7 @@ pub struct CompanyProprietor {
 #[derive(Clone, Debug)]
 pub struct Office {
     /// Universally unique ID for the office.
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
     pub id: Uuid,
     /// Display name for the office, like "Berlin Branch" or "HQ".
     pub name: String,
@@ -123,6 +128,7 @@ pub struct CompanyMember {
     /// Whether this person is currently active in the company.
     pub is_active: bool,
     /// Timestamp when this member was added to the company.
+    #[cfg_attr(all(feature = "facet", not(feature = "chrono")), facet(opaque))]
     pub created_at: NaiveDateTime,
 }
 
@@ -134,14 +140,17 @@ pub struct CompanyMember {
 #[derive(Clone, Debug)]
 pub struct Person {
     /// The person's universal unique identifier.
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
     pub id: Uuid,
     /// Chosen username or unique login handle for this person.
     pub username: String,
     /// Email address associated with this individual.
     pub email: String,
     /// Instant when this profile was first created.
+    #[cfg_attr(all(feature = "facet", not(feature = "chrono")), facet(opaque))]
     pub created_at: NaiveDateTime,
     /// When any property of this profile was last updated.
+    #[cfg_attr(all(feature = "facet", not(feature = "chrono")), facet(opaque))]
     pub updated_at: NaiveDateTime,
     /// Extended personal information.
     pub profile: PersonProfile,
@@ -161,6 +170,7 @@ pub struct PersonProfile {
     /// Person's last or family name.
     pub last_name: String,
     /// Date of birth as an ISO8601 date.
+    #[cfg_attr(all(feature = "facet", not(feature = "chrono")), facet(opaque))]
     pub date_of_birth: NaiveDate,
     /// Self-identified gender or sex for the person.
     pub gender: Sex,
@@ -232,6 +242,7 @@ pub enum Sex {
 #[derive(Clone, Debug)]
 pub struct Item {
     /// Unique identifier for the item.
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
     pub id: Uuid,
     /// Human readable name of the product.
     pub name: String,
@@ -291,6 +302,7 @@ pub struct ItemDimensions {
 #[derive(Clone, Debug)]
 pub struct ItemReview {
     /// Review's unique identifier.
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
     pub id: Uuid,
     /// Summary of the person who wrote the review.
     pub reviewer: PersonSummary,
@@ -299,6 +311,7 @@ pub struct ItemReview {
     /// Optional written text for the review.
     pub text: Option<String>,
     /// Timestamp when the review was posted.
+    #[cfg_attr(all(feature = "facet", not(feature = "chrono")), facet(opaque))]
     pub created_at: NaiveDateTime,
 }
 
@@ -310,6 +323,7 @@ pub struct ItemReview {
 #[derive(Clone, Debug)]
 pub struct Group {
     /// Unique group identifier.
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
     pub id: Uuid,
     /// Name of the group or category.
     pub name: String,
@@ -327,6 +341,7 @@ pub struct Group {
 #[derive(Clone, Debug)]
 pub struct PersonSummary {
     /// Person's unique identifier.
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
     pub id: Uuid,
     /// Chosen username.
     pub username: String,
@@ -342,6 +357,7 @@ pub struct PersonSummary {
 #[derive(Clone, Debug)]
 pub struct Position {
     /// Unique identifier for the position.
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
     pub id: Uuid,
     /// Display name for this position.
     pub name: String,
@@ -359,6 +375,7 @@ pub struct Position {
 #[derive(Clone, Debug)]
 pub struct Right {
     /// Unique identifier for the right or permission.
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
     pub id: Uuid,
     /// Short name describing the right (e.g., "edit_inventory").
     pub name: String,
@@ -374,6 +391,7 @@ pub struct Right {
 #[derive(Clone, Debug)]
 pub struct Preferences {
     /// Person's unique identifier for whom these preferences apply.
+    #[cfg_attr(all(feature = "facet", not(feature = "uuid")), facet(opaque))]
     pub person_id: Uuid,
     /// Whether the user wants to receive email notifications.
     pub email_notifications: bool,
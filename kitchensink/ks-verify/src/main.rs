@@ -0,0 +1,179 @@
+//! Differential correctness check between the facet and serde JSON scenarios.
+//!
+//! Serializes the same mock catalog through both stacks, compares the parsed
+//! JSON structurally, and round-trips each output through the other stack's
+//! reader. Prints one tab-separated line per check (and per difference) to
+//! stdout, which limpid parses:
+//!
+//! ```text
+//! check  <name>  ok
+//! check  <name>  mismatch  <number of differences>
+//! check  <name>  error     <panic message>
+//! diff   <name>  <JSON pointer>  <left value>  <right value>
+//! ```
+
+use std::panic::{self, AssertUnwindSafe};
+
+use serde_json::Value;
+
+/// How many differences are printed per check
+const MAX_DIFFS: usize = 10;
+
+/// How many characters of each differing value are printed
+const MAX_VALUE_LEN: usize = 80;
+
+struct Difference {
+    pointer: String,
+    left: String,
+    right: String,
+}
+
+fn main() {
+    // Readers and writers `unwrap()` on failure: capture those panics as check
+    // errors instead of printing them. Every call into them goes through
+    // `catch`, or a panic would end the run without a word.
+    panic::set_hook(Box::new(|_| {}));
+
+    let catalog = ks_mock::generate_mock_catalog();
+    let facet_json = catch(|| ks_facet_json_write::catalog_to_json(&catalog));
+    let serde_json = catch(|| ks_serde_json_write::catalog_to_json(&catalog));
+
+    check("facet-vs-serde", || {
+        Ok((facet_json.clone()?, serde_json.clone()?))
+    });
+
+    check("facet-to-serde", || {
+        let facet_json = facet_json.clone()?;
+        let catalog = catch(|| ks_serde_json_read::catalog_from_json(&facet_json))?;
+        let serde_json = catch(|| ks_serde_json_write::catalog_to_json(&catalog))?;
+        Ok((facet_json, serde_json))
+    });
+
+    check("serde-to-facet", || {
+        let serde_json = serde_json.clone()?;
+        let catalog = catch(|| ks_facet_json_read::catalog_from_json(&serde_json))?;
+        let facet_json = catch(|| ks_facet_json_write::catalog_to_json(&catalog))?;
+        Ok((serde_json, facet_json))
+    });
+}
+
+/// Run a check producing two JSON documents that should be structurally equal,
+/// and print its outcome.
+fn check(name: &str, f: impl FnOnce() -> Result<(String, String), String>) {
+    let (left, right) = match f() {
+        Ok(docs) => docs,
+        Err(message) => {
+            println!("check\t{name}\terror\t{}", single_line(&message));
+            return;
+        }
+    };
+
+    let parse = |json: &str| serde_json::from_str::<Value>(json).map_err(|e| e.to_string());
+    let (left, right) = match (parse(&left), parse(&right)) {
+        (Ok(left), Ok(right)) => (left, right),
+        (Err(e), _) | (_, Err(e)) => {
+            println!("check\t{name}\terror\tinvalid JSON: {}", single_line(&e));
+            return;
+        }
+    };
+
+    let mut diffs = Vec::new();
+    diff_values("", &left, &right, &mut diffs);
+
+    if diffs.is_empty() {
+        println!("check\t{name}\tok");
+        return;
+    }
+
+    println!("check\t{name}\tmismatch\t{}", diffs.len());
+    for diff in diffs.iter().take(MAX_DIFFS) {
+        println!(
+            "diff\t{name}\t{}\t{}\t{}",
+            diff.pointer, diff.left, diff.right
+        );
+    }
+}
+
+/// Run `f`, turning a panic into an error message
+fn catch<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        if let Some(s) = payload.downcast_ref::<String>() {
+            s.clone()
+        } else if let Some(s) = payload.downcast_ref::<&str>() {
+            s.to_string()
+        } else {
+            "<non-string panic payload>".to_string()
+        }
+    })
+}
+
+/// Recursively compare two JSON values, recording every differing leaf.
+///
+/// Numbers are compared by value, so `1` and `1.0` are considered equal.
+fn diff_values(pointer: &str, left: &Value, right: &Value, out: &mut Vec<Difference>) {
+    match (left, right) {
+        (Value::Object(l), Value::Object(r)) => {
+            let mut keys: Vec<&String> = l.keys().chain(r.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let child = format!("{pointer}/{}", key.replace('~', "~0").replace('/', "~1"));
+                match (l.get(key), r.get(key)) {
+                    (Some(lv), Some(rv)) => diff_values(&child, lv, rv, out),
+                    (lv, rv) => out.push(Difference {
+                        pointer: child,
+                        left: lv.map(show).unwrap_or_else(|| "<missing>".to_string()),
+                        right: rv.map(show).unwrap_or_else(|| "<missing>".to_string()),
+                    }),
+                }
+            }
+        }
+        (Value::Array(l), Value::Array(r)) => {
+            if l.len() != r.len() {
+                out.push(Difference {
+                    pointer: pointer.to_string(),
+                    left: format!("<array of {}>", l.len()),
+                    right: format!("<array of {}>", r.len()),
+                });
+            }
+            for (i, (lv, rv)) in l.iter().zip(r).enumerate() {
+                diff_values(&format!("{pointer}/{i}"), lv, rv, out);
+            }
+        }
+        (Value::Number(l), Value::Number(r)) if l.as_f64() == r.as_f64() => {}
+        (l, r) if l == r => {}
+        (l, r) => out.push(Difference {
+            pointer: pointer.to_string(),
+            left: show(l),
+            right: show(r),
+        }),
+    }
+}
+
+/// Compact, truncated rendering of a JSON value
+fn show(value: &Value) -> String {
+    let s = value.to_string();
+    if s.chars().count() > MAX_VALUE_LEN {
+        let truncated: String = s.chars().take(MAX_VALUE_LEN).collect();
+        format!("{truncated}…")
+    } else {
+        s
+    }
+}
+
+/// Render a (possibly multi-line, colored) message on a single line, keeping
+/// its head and tail if it is too long.
+fn single_line(s: &str) -> String {
+    const KEEP: usize = 120;
+
+    let plain = ks_mock::malformed::strip_ansi(s);
+    let words: Vec<&str> = plain.split_whitespace().collect();
+    let plain: Vec<char> = words.join(" ").chars().collect();
+    if plain.len() <= 2 * KEEP {
+        plain.into_iter().collect()
+    } else {
+        let head: String = plain[..KEEP].iter().collect();
+        let tail: String = plain[plain.len() - KEEP..].iter().collect();
+        format!("{head} … {tail}")
+    }
+}
@@ -0,0 +1,64 @@
+//! Heap allocation counts of the kitchensink scenario binaries
+
+use anyhow::{anyhow, Result};
+use camino::Utf8Path;
+
+use crate::facet_specific::{run_kitchensink_bin, COUNT_ALLOCS_FEATURE, KITCHENSINK_PATH};
+
+/// Allocations made during a single operation
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AllocStats {
+    /// Number of `alloc`/`realloc` calls
+    pub allocations: u64,
+    /// Total bytes requested
+    pub bytes: u64,
+    /// Peak live heap, relative to when the operation started
+    pub peak: u64,
+}
+
+/// Allocations of one scenario binary run
+#[derive(Debug, Clone, Copy)]
+pub struct AllocResult {
+    pub serialize: AllocStats,
+    pub deserialize: AllocStats,
+}
+
+/// Build a scenario binary with the counting allocator and run it once.
+pub fn run_alloc_count(limpid_root: &Utf8Path, manifest: &str, bin: &str) -> Result<AllocResult> {
+    println!(
+        "🧮 Counting allocations of {} in {}...",
+        bin,
+        limpid_root.join(KITCHENSINK_PATH)
+    );
+
+    let stdout = run_kitchensink_bin(limpid_root, manifest, bin, &[COUNT_ALLOCS_FEATURE], &[])?;
+    parse_alloc_output(&stdout)
+}
+
+/// Parse the `alloc` lines printed by `ks_alloc::measure`
+fn parse_alloc_output(stdout: &str) -> Result<AllocResult> {
+    let mut serialize = None;
+    let mut deserialize = None;
+
+    for line in stdout.lines() {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let ["alloc", label, allocations, bytes, peak] = fields.as_slice() else {
+            continue;
+        };
+        let stats = AllocStats {
+            allocations: allocations.parse()?,
+            bytes: bytes.parse()?,
+            peak: peak.parse()?,
+        };
+        match *label {
+            "serialize" => serialize = Some(stats),
+            "deserialize" => deserialize = Some(stats),
+            _ => {}
+        }
+    }
+
+    Ok(AllocResult {
+        serialize: serialize.ok_or_else(|| anyhow!("No allocation count for `serialize`"))?,
+        deserialize: deserialize.ok_or_else(|| anyhow!("No allocation count for `deserialize`"))?,
+    })
+}
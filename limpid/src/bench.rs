@@ -0,0 +1,102 @@
+//! Runtime benchmarks of the kitchensink scenario binaries
+
+use anyhow::{anyhow, Result};
+use camino::Utf8Path;
+use std::time::Duration;
+
+use crate::facet_specific::{
+    run_kitchensink_bin, KITCHENSINK_PATH, KS_FACET_BENCH_BIN, KS_FACET_BENCH_MANIFEST,
+};
+
+/// How many times the benchmark harness should run each operation
+#[derive(Debug, Clone, Copy)]
+pub struct BenchSettings {
+    /// Untimed iterations run before measuring
+    pub warmup: usize,
+    /// Timed iterations
+    pub iterations: usize,
+    /// Size of the benchmarked catalog, see `ks_mock::MockConfig::scaled`
+    pub scale: usize,
+}
+
+/// Timings of a single benchmarked operation
+#[derive(Debug, Clone, Copy)]
+pub struct BenchTiming {
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+/// Results of one benchmark run against a given facet checkout
+#[derive(Debug, Clone)]
+pub struct BenchResult {
+    /// Size of the serialized JSON document, used to compute throughput
+    pub json_bytes: u64,
+    pub serialize: BenchTiming,
+    pub deserialize: BenchTiming,
+}
+
+impl BenchTiming {
+    /// Throughput in bytes per second, based on the median timing
+    pub fn throughput(&self, bytes: u64) -> f64 {
+        bytes as f64 / self.median.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+/// Build and run the `ks-facet-bench` harness in the given limpid checkout.
+pub fn run_benchmark(limpid_root: &Utf8Path, settings: &BenchSettings) -> Result<BenchResult> {
+    println!("⏱️  Benchmarking {}...", limpid_root.join(KITCHENSINK_PATH));
+
+    let stdout = run_kitchensink_bin(
+        limpid_root,
+        KS_FACET_BENCH_MANIFEST,
+        KS_FACET_BENCH_BIN,
+        &[],
+        &[
+            "--warmup".to_string(),
+            settings.warmup.to_string(),
+            "--iterations".to_string(),
+            settings.iterations.to_string(),
+            "--scale".to_string(),
+            settings.scale.to_string(),
+        ],
+    )?;
+
+    parse_bench_output(&stdout)
+}
+
+/// Parse the tab-separated lines printed by the harness
+fn parse_bench_output(stdout: &str) -> Result<BenchResult> {
+    let mut json_bytes = None;
+    let mut serialize = None;
+    let mut deserialize = None;
+
+    for line in stdout.lines() {
+        let fields: Vec<&str> = line.split('\t').collect();
+        match fields.as_slice() {
+            ["bytes", bytes] => json_bytes = Some(bytes.parse()?),
+            ["serialize", rest @ ..] => serialize = Some(parse_timing(rest)?),
+            ["deserialize", rest @ ..] => deserialize = Some(parse_timing(rest)?),
+            _ => {}
+        }
+    }
+
+    Ok(BenchResult {
+        json_bytes: json_bytes.ok_or_else(|| anyhow!("Benchmark output is missing `bytes`"))?,
+        serialize: serialize.ok_or_else(|| anyhow!("Benchmark output is missing `serialize`"))?,
+        deserialize: deserialize
+            .ok_or_else(|| anyhow!("Benchmark output is missing `deserialize`"))?,
+    })
+}
+
+fn parse_timing(fields: &[&str]) -> Result<BenchTiming> {
+    let [median, min, max] = fields else {
+        return Err(anyhow!("Expected 3 timings, got {}", fields.len()));
+    };
+    let nanos = |s: &str| -> Result<Duration> { Ok(Duration::from_nanos(s.parse()?)) };
+    Ok(BenchTiming {
+        median: nanos(median)?,
+        min: nanos(min)?,
+        max: nanos(max)?,
+    })
+}
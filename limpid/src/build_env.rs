@@ -0,0 +1,188 @@
+//! A controlled environment for the builds
+//!
+//! cargo and rustc pick up flags, wrappers and parallelism from the
+//! environment, so a stray `RUSTFLAGS` or an `sccache` wrapper silently skews
+//! sizes and timings. Before building anything, limpid clears these variables
+//! on its own process (which every build inherits), except those kept or set
+//! explicitly with `--build-env`, and the report records what was left.
+
+use anyhow::{anyhow, ensure, Result};
+use owo_colors::OwoColorize;
+
+/// Flags `BuildRunner` sets for the measured build, replacing `RUSTFLAGS`
+const MEASURED_RUSTFLAGS: &str = "--emit=llvm-ir -Cstrip=none";
+
+/// Variables that change what cargo builds, or how long it takes
+const CONTROLLED_VARS: &[&str] = &[
+    "RUSTFLAGS",
+    "CARGO_ENCODED_RUSTFLAGS",
+    "CARGO_BUILD_RUSTFLAGS",
+    "CARGO_INCREMENTAL",
+    "CARGO_BUILD_INCREMENTAL",
+    "RUSTC_WRAPPER",
+    "RUSTC_WORKSPACE_WRAPPER",
+    "CARGO_BUILD_RUSTC_WRAPPER",
+    "CARGO_BUILD_RUSTC_WORKSPACE_WRAPPER",
+    "CARGO_BUILD_JOBS",
+];
+
+/// Profile overrides, e.g. `CARGO_PROFILE_RELEASE_LTO`, are controlled too
+const CONTROLLED_PREFIX: &str = "CARGO_PROFILE_";
+
+/// Variables `BuildRunner` overrides for the measured build: cargo takes
+/// `RUSTFLAGS` over `build.rustflags`, so keeping or setting these would only
+/// reach the other builds, and the report would claim flags that weren't used
+const OVERRIDDEN_VARS: &[&str] = &["RUSTFLAGS", "CARGO_BUILD_RUSTFLAGS"];
+
+/// Wrappers are set to the empty string rather than removed, which also
+/// disables any `build.rustc-wrapper` from cargo config files
+const WRAPPER_VARS: &[&str] = &["RUSTC_WRAPPER", "RUSTC_WORKSPACE_WRAPPER"];
+
+/// A variable given with `--build-env`: `VAR=VALUE` sets it, a bare `VAR`
+/// keeps the value inherited from the environment
+#[derive(Debug, Clone)]
+pub struct BuildEnvOverride {
+    pub name: String,
+    pub value: Option<String>,
+}
+
+impl BuildEnvOverride {
+    pub fn parse(s: &str) -> Result<Self> {
+        let (name, value) = match s.split_once('=') {
+            Some((name, value)) => (name, Some(value.to_string())),
+            None => (s, None),
+        };
+        ensure!(
+            !name.is_empty() && !name.contains(char::is_whitespace),
+            "Invalid --build-env `{}`, expected `VAR=VALUE` or `VAR`",
+            s
+        );
+        ensure!(
+            !OVERRIDDEN_VARS.contains(&name),
+            "--build-env {} can't apply: the measured build always runs with RUSTFLAGS=\"{}\"",
+            name,
+            MEASURED_RUSTFLAGS
+        );
+        Ok(Self {
+            name: name.to_string(),
+            value,
+        })
+    }
+}
+
+fn is_controlled(name: &str) -> bool {
+    CONTROLLED_VARS.contains(&name) || name.starts_with(CONTROLLED_PREFIX)
+}
+
+/// Clear the controlled variables, apply `overrides`, and warn about anything
+/// left that makes build times unreliable
+pub fn sanitize_build_env(overrides: &[BuildEnvOverride]) -> Result<()> {
+    let find_override = |name: &str| overrides.iter().find(|o| o.name == name);
+
+    let inherited: Vec<(String, String)> = std::env::vars()
+        .filter(|(name, _)| is_controlled(name))
+        .collect();
+    for (name, value) in &inherited {
+        if find_override(name).is_none() {
+            println!(
+                "🧹 Ignoring {}={} from the environment",
+                name,
+                value.dimmed()
+            );
+            std::env::remove_var(name);
+        }
+    }
+
+    for var in WRAPPER_VARS {
+        if find_override(var).is_none() {
+            std::env::set_var(var, "");
+        }
+    }
+    if find_override("CARGO_INCREMENTAL").is_none() {
+        std::env::set_var("CARGO_INCREMENTAL", "0");
+    }
+
+    for o in overrides {
+        match &o.value {
+            Some(value) => std::env::set_var(&o.name, value),
+            None => {
+                if std::env::var_os(&o.name).is_none() {
+                    return Err(anyhow!(
+                        "--build-env {} keeps {} from the environment, but it isn't set",
+                        o.name,
+                        o.name
+                    ));
+                }
+            }
+        }
+    }
+
+    for warning in timing_warnings() {
+        eprintln!("⚠️  {}", warning.yellow());
+    }
+
+    Ok(())
+}
+
+/// Anything in the (sanitized) environment that makes build times unreliable
+fn timing_warnings() -> Vec<String> {
+    let mut warnings = Vec::new();
+    let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+
+    for name in [
+        "RUSTC_WRAPPER",
+        "RUSTC_WORKSPACE_WRAPPER",
+        "CARGO_BUILD_RUSTC_WRAPPER",
+        "CARGO_BUILD_RUSTC_WORKSPACE_WRAPPER",
+    ] {
+        if let Some(wrapper) = var(name) {
+            let cache = if wrapper.contains("sccache") || wrapper.contains("cachepot") {
+                "cache hits make build times meaningless"
+            } else {
+                "it adds its own overhead to build times"
+            };
+            warnings.push(format!("{}={} is in use: {}", name, wrapper, cache));
+        }
+    }
+    for name in ["CARGO_INCREMENTAL", "CARGO_BUILD_INCREMENTAL"] {
+        if var(name).is_some_and(|v| v != "0" && v != "false") {
+            warnings.push(format!(
+                "{} enables incremental compilation, which changes codegen",
+                name
+            ));
+        }
+    }
+    if var("CARGO_ENCODED_RUSTFLAGS").is_some() {
+        warnings.push(
+            "CARGO_ENCODED_RUSTFLAGS takes precedence over the flags the measured build needs \
+             for its LLVM IR analysis"
+                .to_string(),
+        );
+    }
+    if let Some(dir) = var("SUBSTANCE_TMP_DIR") {
+        warnings.push(format!(
+            "SUBSTANCE_TMP_DIR={} keeps target directories between runs, which later builds reuse",
+            dir
+        ));
+    }
+
+    warnings
+}
+
+/// The controlled variables that are set, as every build sees them
+pub fn effective_build_env() -> Vec<(String, String)> {
+    let mut vars: Vec<(String, String)> = std::env::vars()
+        .filter(|(name, _)| is_controlled(name))
+        .collect();
+    vars.sort();
+    vars
+}
+
+/// Flags rustc gets in the measured build: cargo prefers
+/// `CARGO_ENCODED_RUSTFLAGS` to the `RUSTFLAGS` that `BuildRunner` sets
+pub fn measured_rustflags() -> String {
+    match std::env::var("CARGO_ENCODED_RUSTFLAGS") {
+        Ok(encoded) if !encoded.is_empty() => encoded.split('\x1f').collect::<Vec<_>>().join(" "),
+        _ => MEASURED_RUSTFLAGS.to_string(),
+    }
+}
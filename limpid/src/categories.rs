@@ -0,0 +1,158 @@
+//! Facet-specific categories of symbols and LLVM functions
+//!
+//! Mangled names tell little about which part of facet grew. The rules of
+//! `kitchensink/categories.txt` sort every symbol and LLVM function into a
+//! category (shape statics, vtable shims, the JSON tokenizer, drop glue...)
+//! so the report can sum bytes and LLVM lines per category.
+
+use anyhow::{anyhow, ensure, Context, Result};
+use camino::Utf8Path;
+use substance::BuildContext;
+
+/// Path to the category rules relative to kitchensink
+pub const CATEGORIES_PATH: &str = "categories.txt";
+
+/// Category of the names no rule matches
+pub const OTHER_CATEGORY: &str = "other";
+
+#[derive(Debug, Clone)]
+enum Pattern {
+    /// `^foo`: the name starts with `foo`
+    Prefix(String),
+    /// `foo`: the name contains `foo`
+    Contains(String),
+}
+
+impl Pattern {
+    fn matches(&self, name: &str) -> bool {
+        match self {
+            Pattern::Prefix(p) => name.starts_with(p.as_str()),
+            Pattern::Contains(p) => name.contains(p.as_str()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CategoryRule {
+    name: String,
+    patterns: Vec<Pattern>,
+}
+
+/// Ordered rules mapping names to categories
+#[derive(Debug, Clone)]
+pub struct CategoryRules {
+    rules: Vec<CategoryRule>,
+}
+
+impl CategoryRules {
+    /// Position in `names()` of the category of a symbol or LLVM function:
+    /// the first rule with a matching pattern, or `other`
+    fn position(&self, name: &str) -> usize {
+        self.rules
+            .iter()
+            .position(|rule| rule.patterns.iter().any(|p| p.matches(name)))
+            .unwrap_or(self.rules.len())
+    }
+
+    /// Category names in the order of the rules, `other` last
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.rules
+            .iter()
+            .map(|rule| rule.name.as_str())
+            .chain(std::iter::once(OTHER_CATEGORY))
+    }
+}
+
+/// Bytes and LLVM lines of one category in one build
+#[derive(Debug, Clone, Default)]
+pub struct CategorySize {
+    pub name: String,
+    /// Size of the symbols in .text
+    pub bytes: u64,
+    pub symbols: usize,
+    pub llvm_lines: usize,
+    pub llvm_functions: usize,
+}
+
+/// Parse category rules
+pub fn parse_category_rules(path: &Utf8Path) -> Result<CategoryRules> {
+    let source =
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path))?;
+
+    let mut rules: Vec<CategoryRule> = Vec::new();
+    for (number, line) in source.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (name, patterns) = line.split_once(": ").ok_or_else(|| {
+            anyhow!(
+                "{}:{}: expected `<category>: <pattern>...`",
+                path,
+                number + 1
+            )
+        })?;
+        let name = name.trim();
+        ensure!(
+            name != OTHER_CATEGORY,
+            "{}:{}: `{}` is the category of unmatched names",
+            path,
+            number + 1,
+            OTHER_CATEGORY
+        );
+        ensure!(
+            rules.iter().all(|rule| rule.name != name),
+            "{}:{}: category `{}` is declared twice",
+            path,
+            number + 1,
+            name
+        );
+
+        let patterns: Vec<Pattern> = patterns
+            .split_whitespace()
+            .map(|p| match p.strip_prefix('^') {
+                Some(prefix) => Pattern::Prefix(prefix.to_string()),
+                None => Pattern::Contains(p.to_string()),
+            })
+            .collect();
+        ensure!(
+            !patterns.is_empty(),
+            "{}:{}: category `{}` has no patterns",
+            path,
+            number + 1,
+            name
+        );
+
+        rules.push(CategoryRule {
+            name: name.to_string(),
+            patterns,
+        });
+    }
+
+    ensure!(!rules.is_empty(), "{} declares no categories", path);
+    Ok(CategoryRules { rules })
+}
+
+/// Sum the symbols and LLVM functions of a build per category, in the order
+/// of the rules
+pub fn category_sizes(rules: &CategoryRules, context: &BuildContext) -> Vec<CategorySize> {
+    let mut sizes: Vec<CategorySize> = rules
+        .names()
+        .map(|name| CategorySize {
+            name: name.to_string(),
+            ..Default::default()
+        })
+        .collect();
+    for sym in context.all_symbols().values() {
+        let size = &mut sizes[rules.position(sym.name.as_str())];
+        size.bytes += sym.total_size.value();
+        size.symbols += 1;
+    }
+    for func in context.all_llvm_functions().values() {
+        let size = &mut sizes[rules.position(func.name.as_str())];
+        size.llvm_lines += func.total_llvm_lines.value();
+        size.llvm_functions += 1;
+    }
+
+    sizes
+}
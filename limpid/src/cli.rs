@@ -2,6 +2,14 @@ use anyhow::{anyhow, Result};
 use camino::Utf8PathBuf;
 use pico_args::Arguments;
 
+use crate::bench::BenchSettings;
+use crate::build_env::BuildEnvOverride;
+use crate::facet_specific::{Scenario, KS_FACET_BIN, SCENARIOS};
+use crate::git::default_target_branch;
+use crate::scaling::ScalingSettings;
+use crate::severity::SeverityThresholds;
+use crate::toolchain::{parse_toolchains, Toolchain};
+
 /// CLI configuration parsed from command-line arguments
 #[derive(Debug, Clone)]
 pub struct CliConfig {
@@ -9,6 +17,37 @@ pub struct CliConfig {
     pub markdown_output: Option<Utf8PathBuf>,
     /// Enable verbose logging
     pub verbose: bool,
+    /// Kitchensink binary whose size and build cost are compared
+    pub scenario: Scenario,
+    /// Run the runtime benchmark phase, if enabled
+    pub bench: Option<BenchSettings>,
+    /// Check that facet and serde agree on the mock catalog
+    pub verify: bool,
+    /// Measure peak rustc memory usage per crate
+    pub rustc_memory: bool,
+    /// Count heap allocations during serialize/deserialize
+    pub allocs: bool,
+    /// Measure build cost of synthetic crates of increasing size, if enabled
+    pub scaling: Option<ScalingSettings>,
+    /// Build the scenario under each combination of the facet feature matrix
+    pub feature_matrix: bool,
+    /// Category rules to use instead of `kitchensink/categories.txt`
+    pub categories: Option<Utf8PathBuf>,
+    /// Relative growth from which report rows are flagged as warnings or critical
+    pub severity: SeverityThresholds,
+    /// Build the current checkouts on both sides, with these (baseline, current) toolchains
+    pub toolchains: Option<(Toolchain, Toolchain)>,
+    /// Only compare sizes, building baseline and current concurrently
+    pub size_only: bool,
+    /// Branch the facet baseline is taken from (default: `origin/$GITHUB_BASE_REF`,
+    /// then `origin/main`)
+    pub target_branch: String,
+    /// Compare to where facet HEAD branched off the target branch, rather than its tip
+    pub merge_base: bool,
+    /// The facet checkout to compare, instead of discovering it
+    pub facet_path: Option<Utf8PathBuf>,
+    /// Build environment variables to keep or set instead of clearing them
+    pub build_env: Vec<BuildEnvOverride>,
 }
 
 impl CliConfig {
@@ -32,8 +71,104 @@ impl CliConfig {
                     .map(Utf8PathBuf::from)
             })?;
 
+        let categories: Option<Utf8PathBuf> = pargs.opt_value_from_os_str("--categories", |s| {
+            s.to_str()
+                .ok_or_else(|| anyhow!("Non-UTF8 path for categories"))
+                .map(Utf8PathBuf::from)
+        })?;
+
+        let facet_path: Option<Utf8PathBuf> = pargs.opt_value_from_os_str("--facet-path", |s| {
+            s.to_str()
+                .ok_or_else(|| anyhow!("Non-UTF8 path for facet"))
+                .map(Utf8PathBuf::from)
+        })?;
+
         let verbose = pargs.contains(["-v", "--verbose"]);
 
+        let scenario_name: Option<String> = pargs.opt_value_from_str(["-s", "--scenario"])?;
+        let scenario_name = scenario_name.as_deref().unwrap_or(KS_FACET_BIN);
+        let scenario = Scenario::find(scenario_name).ok_or_else(|| {
+            anyhow!(
+                "Unknown scenario `{}`. Available scenarios: {}",
+                scenario_name,
+                SCENARIOS
+                    .iter()
+                    .map(|s| s.bin)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })?;
+
+        let verify = pargs.contains("--verify");
+        let rustc_memory = pargs.contains("--rustc-memory");
+        let allocs = pargs.contains("--allocs");
+        let feature_matrix = pargs.contains("--feature-matrix");
+        let size_only = pargs.contains("--size-only");
+        let merge_base = pargs.contains("--merge-base");
+        let target_branch: Option<String> = pargs.opt_value_from_str("--target-branch")?;
+        let target_branch = target_branch.unwrap_or_else(default_target_branch);
+        let build_env: Vec<BuildEnvOverride> =
+            pargs.values_from_fn("--build-env", BuildEnvOverride::parse)?;
+
+        let toolchains: Option<(Toolchain, Toolchain)> =
+            pargs.opt_value_from_fn("--toolchains", parse_toolchains)?;
+
+        let defaults = SeverityThresholds::default();
+        let warn: Option<f64> = pargs.opt_value_from_str("--warn-above")?;
+        let critical: Option<f64> = pargs.opt_value_from_str("--critical-above")?;
+        let severity = SeverityThresholds {
+            warn: warn.unwrap_or(defaults.warn),
+            critical: critical.unwrap_or(defaults.critical),
+        };
+        if !(severity.warn >= 0.0 && severity.warn <= severity.critical) {
+            return Err(anyhow!(
+                "--warn-above must be between 0 and --critical-above ({} > {})",
+                severity.warn,
+                severity.critical
+            ));
+        }
+
+        let bench_enabled = pargs.contains("--bench");
+        let bench_iterations: Option<usize> = pargs.opt_value_from_fn("--bench-iterations", parse_iterations)?;
+        let bench_warmup: Option<usize> = pargs.opt_value_from_str("--bench-warmup")?;
+        let bench_scale: Option<usize> = pargs.opt_value_from_str("--bench-scale")?;
+        let bench = bench_enabled.then(|| BenchSettings {
+            warmup: bench_warmup.unwrap_or(20),
+            iterations: bench_iterations.unwrap_or(200),
+            scale: bench_scale.unwrap_or(1),
+        });
+
+        let scaling_sizes: Option<Vec<usize>> =
+            pargs.opt_value_from_fn("--scaling", parse_sizes)?;
+        let scaling_fields: Option<usize> = pargs.opt_value_from_str("--scaling-fields")?;
+        let scaling_nesting: Option<usize> = pargs.opt_value_from_str("--scaling-nesting")?;
+        let scaling = scaling_sizes.map(|sizes| ScalingSettings {
+            sizes,
+            fields: scaling_fields.unwrap_or(8),
+            nesting: scaling_nesting.unwrap_or(2),
+        });
+
+        if size_only {
+            let timed = [
+                ("--bench", bench.is_some()),
+                ("--scaling", scaling.is_some()),
+                ("--rustc-memory", rustc_memory),
+            ];
+            if let Some((flag, _)) = timed.iter().find(|(_, enabled)| *enabled) {
+                return Err(anyhow!(
+                    "{} can't be combined with --size-only, which builds both sides at once",
+                    flag
+                ));
+            }
+        }
+
+        if toolchains.is_some() && merge_base {
+            return Err(anyhow!(
+                "--merge-base can't be combined with --toolchains, which builds the current \
+                 facet checkout on both sides"
+            ));
+        }
+
         // Any argument left means an unrecognized argument.
         let rest = pargs.finish();
         if !rest.is_empty() {
@@ -49,6 +184,21 @@ impl CliConfig {
         Ok(Self {
             markdown_output,
             verbose,
+            scenario,
+            bench,
+            verify,
+            rustc_memory,
+            allocs,
+            scaling,
+            feature_matrix,
+            categories,
+            severity,
+            toolchains,
+            size_only,
+            target_branch,
+            merge_base,
+            facet_path,
+            build_env,
         })
     }
 
@@ -64,14 +214,88 @@ impl CliConfig {
     }
 }
 
+/// Parse a comma-separated list of type counts, e.g. `10,50,100`
+fn parse_sizes(s: &str) -> Result<Vec<usize>> {
+    let mut sizes = s
+        .split(',')
+        .map(|n| {
+            n.trim()
+                .parse::<usize>()
+                .map_err(|e| anyhow!("Invalid type count `{}`: {}", n, e))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    sizes.sort_unstable();
+    sizes.dedup();
+    if sizes.is_empty() || sizes[0] == 0 {
+        return Err(anyhow!("Type counts must be positive"));
+    }
+    Ok(sizes)
+}
+
+/// Parse `--bench-iterations`: timing no iteration at all has no mean
+fn parse_iterations(s: &str) -> Result<usize> {
+    let iterations = s
+        .trim()
+        .parse::<usize>()
+        .map_err(|e| anyhow!("Invalid iteration count `{}`: {}", s, e))?;
+    if iterations == 0 {
+        return Err(anyhow!("Iteration count must be positive"));
+    }
+    Ok(iterations)
+}
+
 /// Print help message
 fn print_help(program_name: &str) {
     println!("Usage: {} [OPTIONS]", program_name);
     println!();
     println!("OPTIONS:");
-    println!("  -m, --markdown <file>  Generate markdown report to file");
-    println!("  -v, --verbose          Enable verbose logging");
-    println!("  -h, --help             Show this help message");
+    println!("  -m, --markdown <file>       Generate markdown report to file");
+    println!("  -v, --verbose               Enable verbose logging");
+    println!("  -s, --scenario <bin>        Kitchensink binary to analyze (default: ks-facet)");
+    println!("      --rustc-memory          Measure peak rustc memory usage per crate");
+    println!("      --allocs                Count heap allocations during serialize/deserialize");
+    println!("      --verify                Check that facet and serde JSON outputs agree");
+    println!("      --bench                 Benchmark serialize/deserialize runtime");
+    println!("      --bench-iterations <n>  Timed iterations per operation (default: 200)");
+    println!("      --bench-warmup <n>      Untimed warmup iterations (default: 20)");
+    println!(
+        "      --bench-scale <n>       Size multiplier of the benchmarked catalog (default: 1)"
+    );
+    println!("      --scaling <n,n,...>     Build synthetic crates of n types (scaling curve)");
+    println!("      --scaling-fields <n>    Fields per synthetic struct (default: 8)");
+    println!(
+        "      --scaling-nesting <n>   Preceding types a synthetic type may contain (default: 2)"
+    );
+    println!("      --feature-matrix        Build under each facet feature combination of");
+    println!("                              kitchensink/feature-matrix.txt");
+    println!("      --warn-above <pct>      Flag growth of this many percent as a warning");
+    println!("                              (default: 5)");
+    println!("      --critical-above <pct>  Flag growth of this many percent as critical");
+    println!("                              (default: 20)");
+    println!("      --toolchains <a>,<b>    Compare two toolchains (rustup names or rustc");
+    println!("                              paths) on the current facet checkout");
+    println!("      --size-only             Only compare sizes: build baseline and current");
+    println!("                              concurrently and leave out build times");
+    println!(
+        "      --target-branch <ref>   Branch to compare to (default: origin/$GITHUB_BASE_REF"
+    );
+    println!("                              in pull requests, origin/main otherwise)");
+    println!("      --merge-base            Compare to where facet HEAD branched off the target");
+    println!("                              branch, leaving out what was merged there since");
+    println!(
+        "      --facet-path <dir>      facet checkout to compare (default: $LIMPID_FACET_DIR,"
+    );
+    println!("                              then where the kitchensink's path deps lead)");
+    println!("      --build-env <var[=val]> Keep (or set) a build variable that is");
+    println!("                              otherwise cleared, e.g. RUSTC_WRAPPER. Repeatable");
+    println!("      --categories <file>     Symbol category rules (default:");
+    println!("                              kitchensink/categories.txt)");
+    println!("  -h, --help                  Show this help message");
+    println!();
+    println!("SCENARIOS:");
+    for scenario in SCENARIOS {
+        println!("  {:<26}  {}", scenario.bin, scenario.description);
+    }
     println!();
     println!("DESCRIPTION:");
     println!("  Limpid analyzes binary size changes in the Facet serialization framework.");
@@ -85,6 +309,27 @@ fn print_help(program_name: &str) {
     println!("  # Generate a markdown report");
     println!("  {} --markdown report.md", program_name);
     println!();
+    println!("  # Analyze facet-toml instead of facet-json");
+    println!("  {} --scenario ks-facet-toml", program_name);
+    println!();
+    println!("  # Also compare runtime performance");
+    println!("  {} --bench --markdown report.md", program_name);
+    println!();
+    println!("  # See how build cost grows with the number of derived types");
+    println!("  {} --scaling 10,50,100,200", program_name);
+    println!();
+    println!("  # See what each facet feature costs");
+    println!("  {} --feature-matrix", program_name);
+    println!();
+    println!("  # Did the new stable make ks-facet bigger or slower to build?");
+    println!("  {} --toolchains 1.86.0,stable", program_name);
+    println!();
+    println!("  # Quick size check of a pull request's own changes");
+    println!(
+        "  {} --size-only --merge-base --markdown report.md",
+        program_name
+    );
+    println!();
     println!("  # Enable verbose logging");
     println!("  {} --verbose", program_name);
 }
@@ -0,0 +1,127 @@
+//! Differential correctness check between the facet and serde scenarios
+
+use anyhow::{anyhow, Result};
+use camino::Utf8Path;
+
+use crate::facet_specific::{
+    run_kitchensink_bin, KITCHENSINK_PATH, KS_VERIFY_BIN, KS_VERIFY_MANIFEST,
+};
+
+/// A single differing value between two JSON documents
+#[derive(Debug, Clone)]
+pub struct CheckDiff {
+    /// JSON pointer to the differing value
+    pub pointer: String,
+    pub left: String,
+    pub right: String,
+}
+
+/// Outcome of a single check
+#[derive(Debug, Clone)]
+pub enum CheckOutcome {
+    Ok,
+    /// The documents differ; only the first few differences are listed
+    Mismatch {
+        count: usize,
+        diffs: Vec<CheckDiff>,
+    },
+    /// A reader failed (or produced invalid JSON)
+    Error(String),
+}
+
+/// A named check, e.g. `facet-vs-serde`
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub name: String,
+    pub outcome: CheckOutcome,
+}
+
+/// All checks of one run against a given facet checkout
+#[derive(Debug, Clone)]
+pub struct CorrectnessResult {
+    pub checks: Vec<Check>,
+}
+
+impl CorrectnessResult {
+    pub fn get(&self, name: &str) -> Option<&CheckOutcome> {
+        self.checks
+            .iter()
+            .find(|c| c.name == name)
+            .map(|c| &c.outcome)
+    }
+}
+
+/// Human-readable description of a check, with the meaning of its left/right sides
+pub fn describe_check(name: &str) -> (&'static str, &'static str, &'static str) {
+    match name {
+        "facet-vs-serde" => ("facet and serde write the same JSON", "facet", "serde"),
+        "facet-to-serde" => (
+            "facet output survives a serde read/write round-trip",
+            "facet output",
+            "after serde",
+        ),
+        "serde-to-facet" => (
+            "serde output survives a facet read/write round-trip",
+            "serde output",
+            "after facet",
+        ),
+        _ => ("", "left", "right"),
+    }
+}
+
+/// Build and run the `ks-verify` binary in the given limpid checkout.
+pub fn run_correctness_check(limpid_root: &Utf8Path) -> Result<CorrectnessResult> {
+    println!(
+        "🔍 Checking facet/serde agreement in {}...",
+        limpid_root.join(KITCHENSINK_PATH)
+    );
+
+    let stdout = run_kitchensink_bin(limpid_root, KS_VERIFY_MANIFEST, KS_VERIFY_BIN, &[], &[])?;
+    parse_verify_output(&stdout)
+}
+
+/// Parse the tab-separated lines printed by `ks-verify`
+fn parse_verify_output(stdout: &str) -> Result<CorrectnessResult> {
+    let mut checks: Vec<Check> = Vec::new();
+
+    for line in stdout.lines() {
+        let fields: Vec<&str> = line.split('\t').collect();
+        match fields.as_slice() {
+            ["check", name, "ok"] => checks.push(Check {
+                name: name.to_string(),
+                outcome: CheckOutcome::Ok,
+            }),
+            ["check", name, "mismatch", count] => checks.push(Check {
+                name: name.to_string(),
+                outcome: CheckOutcome::Mismatch {
+                    count: count.parse()?,
+                    diffs: Vec::new(),
+                },
+            }),
+            ["check", name, "error", message] => checks.push(Check {
+                name: name.to_string(),
+                outcome: CheckOutcome::Error(message.to_string()),
+            }),
+            ["diff", name, pointer, left, right] => {
+                let check = checks
+                    .iter_mut()
+                    .rfind(|c| c.name == *name)
+                    .ok_or_else(|| anyhow!("Difference reported for unknown check {}", name))?;
+                if let CheckOutcome::Mismatch { diffs, .. } = &mut check.outcome {
+                    diffs.push(CheckDiff {
+                        pointer: pointer.to_string(),
+                        left: left.to_string(),
+                        right: right.to_string(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if checks.is_empty() {
+        return Err(anyhow!("ks-verify did not report any checks"));
+    }
+
+    Ok(CorrectnessResult { checks })
+}
@@ -0,0 +1,148 @@
+//! Resolved dependency graphs of the scenario, and how they differ
+//!
+//! The crate count of the build report doesn't tell which crates a change
+//! added or removed. This resolves the graph with `cargo tree` for both
+//! checkouts, so new transitive dependencies, version changes and duplicate
+//! versions can be listed along with what pulls them in.
+
+use anyhow::{anyhow, ensure, Context, Result};
+use camino::Utf8Path;
+use std::collections::{BTreeMap, BTreeSet};
+use std::process::Command;
+
+use crate::git::run_command;
+
+/// Name and version of a package, e.g. `("ariadne", "0.5.1")`
+pub type PackageId = (String, String);
+
+/// The normal and build dependencies of a scenario, transitively
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    /// Versions of every package in the graph, by name
+    pub versions: BTreeMap<String, BTreeSet<String>>,
+    /// Shortest chain of packages from the scenario down to each package,
+    /// both ends included
+    pub paths: BTreeMap<PackageId, Vec<PackageId>>,
+}
+
+impl DependencyGraph {
+    /// How the given version of `name` is pulled in, as `a → b → name`
+    pub fn path_to(&self, name: &str, version: &str) -> Option<String> {
+        let path = self.paths.get(&(name.to_string(), version.to_string()))?;
+        let names: Vec<&str> = path.iter().map(|(name, _)| name.as_str()).collect();
+        Some(names.join(" → "))
+    }
+}
+
+/// Differences between two dependency graphs
+#[derive(Debug, Clone, Default)]
+pub struct DependencyDiff {
+    /// Packages only in the current graph, with their versions
+    pub added: Vec<(String, BTreeSet<String>)>,
+    /// Packages only in the baseline graph, with their versions
+    pub removed: Vec<(String, BTreeSet<String>)>,
+    /// Packages in both graphs whose versions changed: (name, baseline, current)
+    pub changed: Vec<(String, BTreeSet<String>, BTreeSet<String>)>,
+    /// Packages with several versions in the current graph, and whether that
+    /// was already the case in the baseline
+    pub duplicated: Vec<(String, BTreeSet<String>, bool)>,
+}
+
+impl DependencyDiff {
+    pub fn between(baseline: &DependencyGraph, current: &DependencyGraph) -> Self {
+        let mut diff = DependencyDiff::default();
+
+        for (name, versions) in &current.versions {
+            match baseline.versions.get(name) {
+                None => diff.added.push((name.clone(), versions.clone())),
+                Some(old) if old != versions => {
+                    diff.changed
+                        .push((name.clone(), old.clone(), versions.clone()))
+                }
+                Some(_) => {}
+            }
+            if versions.len() > 1 {
+                let before = baseline.versions.get(name).is_some_and(|v| v.len() > 1);
+                diff.duplicated
+                    .push((name.clone(), versions.clone(), before));
+            }
+        }
+        for (name, versions) in &baseline.versions {
+            if !current.versions.contains_key(name) {
+                diff.removed.push((name.clone(), versions.clone()));
+            }
+        }
+
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Resolve the dependency graph of a scenario manifest with `cargo tree`.
+pub fn resolve_dependency_graph(manifest: &Utf8Path) -> Result<DependencyGraph> {
+    let mut cmd = Command::new("cargo");
+    cmd.args(["tree", "--manifest-path"])
+        .arg(manifest.as_str())
+        .args([
+            "--edges",
+            "normal,build",
+            "--prefix",
+            "depth",
+            "--format",
+            "{p}",
+        ]);
+
+    let output = run_command(&mut cmd)?;
+    ensure!(
+        output.status.success(),
+        "cargo tree failed for {}: {}",
+        manifest,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8(output.stdout).context("Invalid UTF-8 in cargo tree output")?;
+
+    parse_cargo_tree(&stdout)
+}
+
+/// Parse `cargo tree --prefix depth --format {p}` output, where each line is
+/// `<depth><name> v<version>[ (<source>)][ (*)]`
+fn parse_cargo_tree(stdout: &str) -> Result<DependencyGraph> {
+    let mut graph = DependencyGraph::default();
+    // Packages from the root down to the line being parsed
+    let mut stack: Vec<PackageId> = Vec::new();
+
+    for line in stdout.lines() {
+        let digits = line.bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            continue;
+        }
+        let depth: usize = line[..digits].parse()?;
+        let mut words = line[digits..].split_whitespace();
+        let (Some(name), Some(version)) = (words.next(), words.next()) else {
+            return Err(anyhow!("Unexpected cargo tree line: {}", line));
+        };
+        let id: PackageId = (
+            name.to_string(),
+            version.trim_start_matches('v').to_string(),
+        );
+
+        stack.truncate(depth);
+        stack.push(id.clone());
+
+        graph
+            .versions
+            .entry(id.0.clone())
+            .or_default()
+            .insert(id.1.clone());
+        let path = graph.paths.entry(id).or_insert_with(|| stack.clone());
+        if stack.len() < path.len() {
+            *path = stack.clone();
+        }
+    }
+
+    ensure!(!graph.versions.is_empty(), "cargo tree listed no packages");
+    Ok(graph)
+}
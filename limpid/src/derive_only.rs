@@ -0,0 +1,194 @@
+//! Check that `Facet` derives of unused types cost nothing in the final binary
+//!
+//! `ks_types::unused` derives `Facet` for a type hierarchy that no scenario
+//! references. The `ks-facet-derive` scenario links ks-types with the facet
+//! feature without touching it; this module scans the resulting executable
+//! for anything those types could leave behind:
+//!
+//! - symbols whose path goes through `ks_types::unused`
+//! - the doc comments that facet embeds in shapes, which are the most
+//!   distinctive part of shape data (type names like `Item` are too generic to
+//!   be searched for on their own)
+
+use anyhow::{anyhow, ensure, Context, Result};
+use camino::Utf8Path;
+use std::collections::BTreeSet;
+
+use crate::facet_specific::{
+    build_kitchensink_bin, KITCHENSINK_PATH, KS_FACET_DERIVE_BIN, KS_FACET_DERIVE_MANIFEST,
+};
+
+/// Source of the unused type hierarchy, relative to kitchensink
+const UNUSED_TYPES_SOURCE: &str = "ks-types/src/unused.rs";
+
+/// Source of the types the scenarios do use, relative to kitchensink
+const USED_TYPES_SOURCE: &str = "ks-types/src/lib.rs";
+
+/// A type declared in a ks-types source file, with its doc comment lines
+/// (including the ones of its fields and variants)
+struct DeclaredType {
+    name: String,
+    docs: Vec<String>,
+}
+
+/// An unused type that left traces in the binary
+#[derive(Debug, Clone)]
+pub struct SurvivingType {
+    pub name: String,
+    /// Number of symbols mentioning the type
+    pub symbols: usize,
+    /// Number of the type's doc comment lines found in shape data
+    pub docs: usize,
+}
+
+/// Result of scanning one build of the derive-only scenario
+#[derive(Debug, Clone)]
+pub struct DeriveOnlyResult {
+    /// Number of unused types looked for
+    pub types_checked: usize,
+    pub survivors: Vec<SurvivingType>,
+}
+
+impl DeriveOnlyResult {
+    /// Survivors that `baseline` did not have
+    pub fn new_survivors<'a>(&'a self, baseline: &DeriveOnlyResult) -> Vec<&'a SurvivingType> {
+        self.survivors
+            .iter()
+            .filter(|s| !baseline.survivors.iter().any(|b| b.name == s.name))
+            .collect()
+    }
+}
+
+/// Build the derive-only scenario in the given limpid checkout and scan it for
+/// traces of `ks_types::unused`.
+pub fn run_derive_only_check(limpid_root: &Utf8Path) -> Result<DeriveOnlyResult> {
+    let kitchensink = limpid_root.join(KITCHENSINK_PATH);
+    println!(
+        "🧹 Checking that unused derived types are dropped in {}...",
+        kitchensink
+    );
+
+    let unused = parse_declared_types(&kitchensink.join(UNUSED_TYPES_SOURCE))?;
+    let used = parse_declared_types(&kitchensink.join(USED_TYPES_SOURCE))?;
+
+    let binary_path =
+        build_kitchensink_bin(limpid_root, KS_FACET_DERIVE_MANIFEST, KS_FACET_DERIVE_BIN)?;
+    let binary =
+        std::fs::read(&binary_path).with_context(|| format!("Failed to read {}", binary_path))?;
+
+    // The scenario prints the shapes of `Catalog`: if its docs can't be found,
+    // shapes don't carry docs (anymore) and the scan proves nothing
+    let catalog = used
+        .iter()
+        .find(|t| t.name == "Catalog")
+        .ok_or_else(|| anyhow!("`Catalog` not found in {}", USED_TYPES_SOURCE))?;
+    ensure!(
+        catalog.docs.iter().any(|doc| contains(&binary, doc)),
+        "No shape docs of `Catalog` found in {}, can't tell whether unused shapes were dropped",
+        binary_path
+    );
+
+    // The unused hierarchy mirrors the used one, so some doc lines are shared
+    let used_docs: BTreeSet<&str> = used
+        .iter()
+        .flat_map(|t| t.docs.iter().map(String::as_str))
+        .collect();
+
+    let mut survivors = Vec::new();
+    for ty in &unused {
+        let mangled = format!("ks_types6unused{}{}", ty.name.len(), ty.name);
+        let generic_arg = format!("ks_types..unused..{}", ty.name);
+        let symbols =
+            count_occurrences(&binary, &mangled) + count_word_occurrences(&binary, &generic_arg);
+
+        let docs = ty
+            .docs
+            .iter()
+            .filter(|doc| !used_docs.contains(doc.as_str()))
+            .filter(|doc| contains(&binary, doc))
+            .count();
+
+        if symbols > 0 || docs > 0 {
+            survivors.push(SurvivingType {
+                name: ty.name.clone(),
+                symbols,
+                docs,
+            });
+        }
+    }
+
+    Ok(DeriveOnlyResult {
+        types_checked: unused.len(),
+        survivors,
+    })
+}
+
+/// Collect the `pub struct`/`pub enum` declarations of a source file, along
+/// with the doc comments on them and on their fields/variants.
+fn parse_declared_types(path: &Utf8Path) -> Result<Vec<DeclaredType>> {
+    let source =
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path))?;
+
+    let mut types: Vec<DeclaredType> = Vec::new();
+    let mut pending_docs = Vec::new();
+    let mut in_body = false;
+
+    for line in source.lines() {
+        let trimmed = line.trim();
+        if let Some(doc) = trimmed.strip_prefix("///") {
+            let doc = doc.trim();
+            if doc.is_empty() {
+                continue;
+            }
+            match types.last_mut() {
+                Some(current) if in_body => current.docs.push(doc.to_string()),
+                _ => pending_docs.push(doc.to_string()),
+            }
+        } else if let Some(decl) = trimmed
+            .strip_prefix("pub struct ")
+            .or_else(|| trimmed.strip_prefix("pub enum "))
+        {
+            let name: String = decl
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '_')
+                .collect();
+            types.push(DeclaredType {
+                name,
+                docs: std::mem::take(&mut pending_docs),
+            });
+            in_body = true;
+        } else if trimmed == "}" {
+            in_body = false;
+        }
+    }
+
+    Ok(types)
+}
+
+fn contains(haystack: &[u8], needle: &str) -> bool {
+    count_occurrences(haystack, needle) > 0
+}
+
+fn count_occurrences(haystack: &[u8], needle: &str) -> usize {
+    let needle = needle.as_bytes();
+    haystack
+        .windows(needle.len())
+        .filter(|w| *w == needle)
+        .count()
+}
+
+/// Like `count_occurrences`, but skips matches that are followed by an
+/// identifier character (so `Item` doesn't match `ItemReview`)
+fn count_word_occurrences(haystack: &[u8], needle: &str) -> usize {
+    let needle = needle.as_bytes();
+    haystack
+        .windows(needle.len())
+        .enumerate()
+        .filter(|(_, w)| *w == needle)
+        .filter(|(i, _)| {
+            haystack
+                .get(i + needle.len())
+                .is_none_or(|c| !(c.is_ascii_alphanumeric() || *c == b'_'))
+        })
+        .count()
+}
@@ -1,8 +1,12 @@
 //! Facet-specific paths and configuration
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, ensure, Context, Result};
 use camino::{Utf8Path, Utf8PathBuf};
 use owo_colors::OwoColorize;
+use std::process::Command;
+
+use crate::git::{find_git_root, run_command};
+use crate::workspace_deps::KitchensinkManifest;
 
 /// Path to the kitchensink directory relative to limpid root
 pub const KITCHENSINK_PATH: &str = "kitchensink";
@@ -10,30 +14,312 @@ pub const KITCHENSINK_PATH: &str = "kitchensink";
 /// Path to the ks-facet manifest relative to kitchensink
 pub const KS_FACET_MANIFEST: &str = "ks-facet/Cargo.toml";
 
-/// Find the Facet workspace given the Limpid repository root
-pub fn find_facet_workspace(limpid_root: &Utf8Path) -> Result<Utf8PathBuf> {
-    // Facet should be in the parent directory of limpid
-    let workspace_root = limpid_root
-        .parent()
-        .ok_or_else(|| anyhow!("Could not find parent of limpid repository"))?;
+/// Name of the ks-facet scenario binary
+pub const KS_FACET_BIN: &str = "ks-facet";
+
+/// A kitchensink binary whose size and build cost limpid can analyze
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scenario {
+    /// Name of the binary, which is also the name of its package directory
+    pub bin: &'static str,
+    /// What the scenario exercises
+    pub description: &'static str,
+}
+
+/// All scenarios that can be passed to `--scenario`
+pub const SCENARIOS: &[Scenario] = &[
+    Scenario {
+        bin: KS_FACET_BIN,
+        description: "facet-json read/write and facet-pretty",
+    },
+    Scenario {
+        bin: "ks-facet-extra",
+        description: "facet-json read/write of the second type set (ks_types::extra)",
+    },
+    Scenario {
+        bin: "ks-serde-extra",
+        description: "serde_json read/write of the second type set, serde counterpart",
+    },
+    Scenario {
+        bin: "ks-facet-toml",
+        description: "facet-toml read/write of a store config, facet-pretty",
+    },
+    Scenario {
+        bin: "ks-serde-toml",
+        description: "toml read/write of a store config, serde counterpart",
+    },
+    Scenario {
+        bin: "ks-facet-reflect",
+        description: "Peek/Partial walk and rebuild, no format crate",
+    },
+    Scenario {
+        bin: KS_FACET_DERIVE_BIN,
+        description: "Facet derives only, checks unused types are dropped",
+    },
+    Scenario {
+        bin: KS_FACET_ERRORS_BIN,
+        description: "facet-json errors on malformed input, vs serde_json",
+    },
+];
+
+impl Scenario {
+    /// Look up a scenario by binary name
+    pub fn find(bin: &str) -> Option<Scenario> {
+        SCENARIOS.iter().find(|s| s.bin == bin).copied()
+    }
+
+    /// Path to the scenario's manifest relative to kitchensink
+    pub fn manifest(&self) -> String {
+        format!("{}/Cargo.toml", self.bin)
+    }
+}
+
+/// Path to the derive-only scenario manifest relative to kitchensink
+pub const KS_FACET_DERIVE_MANIFEST: &str = "ks-facet-derive/Cargo.toml";
+
+/// Name of the derive-only scenario binary
+pub const KS_FACET_DERIVE_BIN: &str = "ks-facet-derive";
+
+/// Path to the malformed-input scenario manifest relative to kitchensink
+pub const KS_FACET_ERRORS_MANIFEST: &str = "ks-facet-errors/Cargo.toml";
+
+/// Name of the malformed-input scenario binary
+pub const KS_FACET_ERRORS_BIN: &str = "ks-facet-errors";
+
+/// Path to the serde_json counterpart of the malformed-input scenario
+pub const KS_SERDE_ERRORS_MANIFEST: &str = "ks-serde-errors/Cargo.toml";
+
+/// Name of the serde_json counterpart of the malformed-input scenario
+pub const KS_SERDE_ERRORS_BIN: &str = "ks-serde-errors";
+
+/// Path to the ks-serde manifest relative to kitchensink
+pub const KS_SERDE_MANIFEST: &str = "ks-serde/Cargo.toml";
+
+/// Name of the ks-serde scenario binary
+pub const KS_SERDE_BIN: &str = "ks-serde";
+
+/// Feature of the scenario binaries that installs an allocation-counting allocator
+pub const COUNT_ALLOCS_FEATURE: &str = "count-allocs";
+
+/// Path to the runtime benchmark harness manifest relative to kitchensink
+pub const KS_FACET_BENCH_MANIFEST: &str = "ks-facet-bench/Cargo.toml";
 
-    let facet_root = workspace_root.join("facet");
+/// Name of the runtime benchmark harness binary
+pub const KS_FACET_BENCH_BIN: &str = "ks-facet-bench";
 
-    if facet_root.exists() && facet_root.join(".git").exists() {
-        Ok(facet_root)
+/// Path to the facet/serde differential check manifest relative to kitchensink
+pub const KS_VERIFY_MANIFEST: &str = "ks-verify/Cargo.toml";
+
+/// Name of the facet/serde differential check binary
+pub const KS_VERIFY_BIN: &str = "ks-verify";
+
+/// Environment variable pointing at the facet checkout, if `--facet-path` isn't given
+pub const FACET_DIR_ENV: &str = "LIMPID_FACET_DIR";
+
+/// A facet crate the kitchensink depends on by path
+struct FacetPathDep {
+    name: String,
+    /// The path as written in the manifest, relative to the kitchensink
+    path: String,
+    /// Where that path leads, canonicalized if it exists
+    resolved: Utf8PathBuf,
+}
+
+/// The facet entries of `[workspace.dependencies]` in `kitchensink/Cargo.toml`
+/// that have a `path`, e.g. `facet-json = { path = "../../facet/facet-json" }`
+fn kitchensink_facet_deps(manifest: &KitchensinkManifest) -> Vec<FacetPathDep> {
+    let kitchensink_dir = manifest.path.parent().unwrap_or(Utf8Path::new("."));
+    manifest
+        .dependencies
+        .iter()
+        .filter(|dep| dep.name == "facet" || dep.name.starts_with("facet-"))
+        .filter_map(|dep| {
+            let path = dep.path()?;
+            Some(FacetPathDep {
+                name: dep.name.clone(),
+                path: path.to_string(),
+                resolved: canonicalize(&kitchensink_dir.join(path)),
+            })
+        })
+        .collect()
+}
+
+/// The canonical form of `path` if it exists, `path` itself otherwise
+fn canonicalize(path: &Utf8Path) -> Utf8PathBuf {
+    path.canonicalize_utf8()
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Find the facet checkout to compare, from (in order) `--facet-path`,
+/// `LIMPID_FACET_DIR`, the kitchensink's path dependencies, or a `facet/`
+/// checkout next to the limpid repository.
+pub fn find_facet_workspace(
+    limpid_root: &Utf8Path,
+    facet_path: Option<&Utf8Path>,
+) -> Result<Utf8PathBuf> {
+    let env_path = std::env::var(FACET_DIR_ENV)
+        .ok()
+        .filter(|dir| !dir.is_empty());
+
+    let (facet_root, source) = if let Some(path) = facet_path {
+        (path.to_path_buf(), "--facet-path".to_string())
+    } else if let Some(dir) = env_path {
+        (Utf8PathBuf::from(dir), FACET_DIR_ENV.to_string())
+    } else if let Some(dep) = kitchensink_facet_deps(&KitchensinkManifest::read(
+        &limpid_root.join(KITCHENSINK_PATH),
+    )?)
+    .into_iter()
+    .find(|dep| dep.resolved.exists())
+    {
+        (
+            find_git_root(&dep.resolved)?,
+            format!("the `{}` path dependency of the kitchensink", dep.name),
+        )
     } else {
-        Err(anyhow!(
-            "Facet repository not found at {}. Expected directory structure:\n\
+        let workspace_root = limpid_root
+            .parent()
+            .ok_or_else(|| anyhow!("Could not find parent of limpid repository"))?;
+        let facet_root = workspace_root.join("facet");
+        ensure!(
+            facet_root.join(".git").exists(),
+            "Facet repository not found at {}. Pass --facet-path <dir>, set {}, or use \
+             the directory structure:\n\
              workspace/\n\
              ├── facet/\n\
              └── limpid/",
+            facet_root,
+            FACET_DIR_ENV
+        );
+        (facet_root, "the directory next to limpid".to_string())
+    };
+
+    ensure!(
+        facet_root.join(".git").exists(),
+        "{} (from {}) is not a git checkout of facet",
+        facet_root,
+        source
+    );
+
+    println!(
+        "{} {}",
+        format!("✅ Found facet via {}:", source).bright_black(),
+        facet_root.green()
+    );
+
+    Ok(canonicalize(&facet_root))
+}
+
+/// Make the kitchensink of `limpid_root` build the facet checkout at
+/// `facet_root`.
+///
+/// If its facet path dependencies lead elsewhere, the kitchensink is copied to
+/// a temporary directory with those dependencies pointed at the same crates in
+/// `facet_root` (facet keeps each crate in a top-level directory named after
+/// it). The returned directory then stands in for `limpid_root` in the builds.
+/// Returns `None` if the path dependencies already lead into `facet_root`.
+pub fn redirect_facet_path_deps(
+    limpid_root: &Utf8Path,
+    facet_root: &Utf8Path,
+) -> Result<Option<tempfile::TempDir>> {
+    let facet_root = canonicalize(facet_root);
+    let kitchensink = limpid_root.join(KITCHENSINK_PATH);
+    let mut manifest = KitchensinkManifest::read(&kitchensink)?;
+    let deps = kitchensink_facet_deps(&manifest);
+    let mismatched: Vec<&FacetPathDep> = deps
+        .iter()
+        .filter(|dep| !dep.resolved.exists() || !dep.resolved.starts_with(&facet_root))
+        .collect();
+    if mismatched.is_empty() {
+        return Ok(None);
+    }
+
+    println!(
+        "{}",
+        format!(
+            "🔀 The kitchensink of {} depends on facet crates outside {}:",
+            limpid_root, facet_root
+        )
+        .bright_black()
+    );
+    for dep in &mismatched {
+        println!(
+            "   {} = \"{}\" → {} (outside {})",
+            dep.name.yellow(),
+            dep.path,
+            dep.resolved,
             facet_root
-        ))
+        );
+    }
+
+    // The copy is elsewhere, so relative paths that did lead into `facet_root`
+    // must be rewritten too
+    let mut missing = Vec::new();
+    for dep in &deps {
+        let crate_dir = Utf8Path::new(&dep.path)
+            .file_name()
+            .ok_or_else(|| anyhow!("`{}` has no directory in its path", dep.name))?;
+        let redirected = facet_root.join(crate_dir);
+        if !redirected.join("Cargo.toml").exists() {
+            missing.push(format!("  {} (no {})", dep.name, redirected));
+            continue;
+        }
+        if let Some(entry) = manifest.get_mut(&dep.name) {
+            entry.set("path", format!("\"{}\"", redirected));
+        }
     }
+    ensure!(
+        missing.is_empty(),
+        "The facet checkout at {} lacks crates the kitchensink depends on:\n{}",
+        facet_root,
+        missing.join("\n")
+    );
+
+    let tmp_dir = tempfile::Builder::new()
+        .prefix("limpid-redirected")
+        .tempdir()
+        .context("Failed to create directory for the kitchensink copy")?;
+    let root = Utf8Path::from_path(tmp_dir.path())
+        .ok_or_else(|| anyhow!("Temporary directory is not valid UTF-8"))?;
+    let copy = root.join(KITCHENSINK_PATH);
+    std::fs::create_dir_all(&copy)?;
+    copy_kitchensink(&kitchensink, &copy)?;
+    std::fs::write(copy.join("Cargo.toml"), manifest.render())
+        .with_context(|| format!("Failed to write {}", copy.join("Cargo.toml")))?;
+
+    println!(
+        "{} {}",
+        "🔀 Building a copy of the kitchensink pointed at it:".bright_black(),
+        copy.green()
+    );
+
+    Ok(Some(tmp_dir))
+}
+
+/// Copy the kitchensink sources (and lockfile) without its target directory
+pub fn copy_kitchensink(from: &Utf8Path, to: &Utf8Path) -> Result<()> {
+    for entry in from.read_dir_utf8()? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if name == "target" || name.starts_with('.') {
+            continue;
+        }
+        let dest = to.join(name);
+        if entry.file_type()?.is_dir() {
+            std::fs::create_dir_all(&dest)?;
+            copy_kitchensink(entry.path(), &dest)?;
+        } else {
+            std::fs::copy(entry.path(), &dest)
+                .with_context(|| format!("Failed to copy {}", entry.path()))?;
+        }
+    }
+    Ok(())
 }
 
 /// Verify that the kitchensink structure exists and is valid
-pub fn verify_kitchensink_structure(limpid_root: &Utf8Path) -> Result<Utf8PathBuf> {
+pub fn verify_kitchensink_structure(
+    limpid_root: &Utf8Path,
+    scenario: &Scenario,
+) -> Result<Utf8PathBuf> {
     let kitchensink_dir = limpid_root.join(KITCHENSINK_PATH);
 
     if !kitchensink_dir.exists() {
@@ -49,19 +335,116 @@ pub fn verify_kitchensink_structure(limpid_root: &Utf8Path) -> Result<Utf8PathBu
         kitchensink_dir.green()
     );
 
-    let ks_facet_manifest = kitchensink_dir.join(KS_FACET_MANIFEST);
-    if !ks_facet_manifest.exists() {
+    let scenario_manifest = kitchensink_dir.join(scenario.manifest());
+    if !scenario_manifest.exists() {
         return Err(anyhow!(
-            "ks-facet manifest not found at: {}",
-            ks_facet_manifest.red()
+            "{} manifest not found at: {}",
+            scenario.bin,
+            scenario_manifest.red()
         ));
     }
 
     println!(
         "{} {}",
-        "✅ Found ks-facet manifest:".bright_black(),
-        ks_facet_manifest.green()
+        format!("✅ Found {} manifest:", scenario.bin).bright_black(),
+        scenario_manifest.green()
+    );
+
+    Ok(scenario_manifest)
+}
+
+/// Build a kitchensink binary in release mode (with the given cargo features),
+/// run it with the given arguments and return its standard output.
+pub fn run_kitchensink_bin(
+    limpid_root: &Utf8Path,
+    manifest: &str,
+    bin: &str,
+    features: &[&str],
+    args: &[String],
+) -> Result<String> {
+    let manifest_path = limpid_root.join(KITCHENSINK_PATH).join(manifest);
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--release", "--quiet", "--manifest-path"])
+        .arg(manifest_path.as_str())
+        .args(["--bin", bin]);
+    if !features.is_empty() {
+        cmd.arg("--features").arg(features.join(","));
+    }
+    cmd.arg("--").args(args);
+
+    let output = run_command(&mut cmd)?;
+
+    ensure!(
+        output.status.success(),
+        "{} failed: {}",
+        bin,
+        String::from_utf8_lossy(&output.stderr)
     );
 
-    Ok(ks_facet_manifest)
+    let stdout = String::from_utf8(output.stdout)
+        .with_context(|| format!("Invalid UTF-8 in {} output", bin))?;
+
+    Ok(stdout)
+}
+
+/// Build a kitchensink binary in release mode and return the path to the executable.
+pub fn build_kitchensink_bin(
+    limpid_root: &Utf8Path,
+    manifest: &str,
+    bin: &str,
+) -> Result<Utf8PathBuf> {
+    let kitchensink = limpid_root.join(KITCHENSINK_PATH);
+    let manifest_path = kitchensink.join(manifest);
+    // Explicit, so the executable is found regardless of `CARGO_TARGET_DIR`
+    let target_dir = kitchensink.join("target");
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["build", "--release", "--quiet", "--manifest-path"])
+        .arg(manifest_path.as_str())
+        .arg("--target-dir")
+        .arg(target_dir.as_str())
+        .args(["--bin", bin]);
+
+    let output = run_command(&mut cmd)?;
+
+    ensure!(
+        output.status.success(),
+        "Building {} failed: {}",
+        bin,
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let executable = target_dir
+        .join("release")
+        .join(bin)
+        .with_extension(std::env::consts::EXE_EXTENSION);
+    ensure!(
+        executable.exists(),
+        "{} was built but not found at {}",
+        bin,
+        executable
+    );
+
+    Ok(executable)
+}
+
+/// Size of the `.text` section of an executable (`__text` on macOS)
+pub fn text_size(executable: &Utf8Path) -> Result<u64> {
+    let data =
+        std::fs::read(executable).with_context(|| format!("Failed to read {}", executable))?;
+
+    let (_, text_size) = match binfarce::detect_format(&data) {
+        binfarce::Format::Elf32 { byte_order } => {
+            binfarce::elf32::parse(&data, byte_order)?.symbols(".text")?
+        }
+        binfarce::Format::Elf64 { byte_order } => {
+            binfarce::elf64::parse(&data, byte_order)?.symbols(".text")?
+        }
+        binfarce::Format::Macho => binfarce::macho::parse(&data)?.symbols()?,
+        binfarce::Format::PE => binfarce::pe::parse(&data)?.symbols()?,
+        binfarce::Format::Unknown => bail!("Unsupported executable format: {}", executable),
+    };
+
+    Ok(text_size)
 }
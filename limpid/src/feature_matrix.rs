@@ -0,0 +1,204 @@
+//! Build cost of the facet cargo features
+//!
+//! `kitchensink/feature-matrix.txt` declares combinations of features of the
+//! facet crates (and of ks-types, whose features pull in facet's chrono and
+//! uuid impls). For each one, the kitchensink is copied to a temporary
+//! directory, its `[workspace.dependencies]` are rewritten to turn default
+//! features off or enable extra features, and the scenario binary is built and
+//! measured.
+//! Only the current checkout is measured: the combinations are compared to
+//! each other, not to the baseline.
+
+use anyhow::{anyhow, ensure, Context, Result};
+use camino::Utf8Path;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+use substance::BuildRunner;
+
+use crate::facet_specific::{copy_kitchensink, Scenario, KITCHENSINK_PATH};
+use crate::workspace_deps::KitchensinkManifest;
+
+/// Path to the feature matrix declaration relative to kitchensink
+pub const FEATURE_MATRIX_PATH: &str = "feature-matrix.txt";
+
+/// A named set of changes to the facet dependencies of the kitchensink
+#[derive(Debug, Clone)]
+pub struct FeatureCombination {
+    pub name: String,
+    /// Crates whose default features are turned off
+    pub no_default: BTreeSet<String>,
+    /// Extra features enabled, per crate
+    pub features: BTreeMap<String, Vec<String>>,
+}
+
+impl FeatureCombination {
+    /// The changes as written in the matrix file, e.g. `facet-json/-default facet/reflect`
+    pub fn changes(&self) -> String {
+        let no_default = self.no_default.iter().map(|c| format!("{}/-default", c));
+        let features = self
+            .features
+            .iter()
+            .flat_map(|(c, fs)| fs.iter().map(move |f| format!("{}/{}", c, f)));
+        no_default.chain(features).collect::<Vec<_>>().join(" ")
+    }
+}
+
+/// Build cost of the scenario under one feature combination
+#[derive(Debug, Clone)]
+pub struct FeaturePoint {
+    pub combination: FeatureCombination,
+    pub wall_duration: Duration,
+    pub file_size: u64,
+    pub text_size: u64,
+    pub llvm_lines: usize,
+    /// Number of crates in the build, std excluded
+    pub crates: usize,
+}
+
+/// Parse the feature matrix declared in the given kitchensink
+pub fn parse_feature_matrix(kitchensink: &Utf8Path) -> Result<Vec<FeatureCombination>> {
+    let path = kitchensink.join(FEATURE_MATRIX_PATH);
+    let source =
+        std::fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path))?;
+    let known: BTreeSet<String> = KitchensinkManifest::read(kitchensink)?
+        .dependencies
+        .into_iter()
+        .map(|dep| dep.name)
+        .collect();
+
+    let mut combinations = Vec::new();
+    for (number, line) in source.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (name, changes) = line
+            .split_once(':')
+            .ok_or_else(|| anyhow!("{}:{}: expected `<name>: <changes>`", path, number + 1))?;
+
+        let mut combination = FeatureCombination {
+            name: name.trim().to_string(),
+            no_default: BTreeSet::new(),
+            features: BTreeMap::new(),
+        };
+        for change in changes.split_whitespace() {
+            let (krate, feature) = change.split_once('/').ok_or_else(|| {
+                anyhow!(
+                    "{}:{}: expected `<crate>/<feature>`, got `{}`",
+                    path,
+                    number + 1,
+                    change
+                )
+            })?;
+            ensure!(
+                known.contains(krate),
+                "{}:{}: `{}` is not a workspace dependency of the kitchensink",
+                path,
+                number + 1,
+                krate
+            );
+            if feature == "-default" {
+                combination.no_default.insert(krate.to_string());
+            } else {
+                combination
+                    .features
+                    .entry(krate.to_string())
+                    .or_default()
+                    .push(feature.to_string());
+            }
+        }
+        combinations.push(combination);
+    }
+
+    ensure!(
+        !combinations.is_empty(),
+        "{} declares no combinations",
+        path
+    );
+    Ok(combinations)
+}
+
+/// Build the scenario under every combination of the given limpid checkout's
+/// feature matrix.
+pub fn run_feature_matrix(
+    limpid_root: &Utf8Path,
+    scenario: &Scenario,
+) -> Result<Vec<FeaturePoint>> {
+    let kitchensink = limpid_root.join(KITCHENSINK_PATH);
+    let combinations = parse_feature_matrix(&kitchensink)?;
+
+    let mut points = Vec::new();
+    for combination in combinations {
+        println!(
+            "🎛️  Building {} with facet features `{}`...",
+            scenario.bin, combination.name
+        );
+
+        // Removed once the build is analyzed
+        let tmp_dir = tempfile::Builder::new()
+            .prefix("limpid-features")
+            .tempdir()
+            .context("Failed to create directory for the kitchensink copy")?;
+        let copy = Utf8Path::from_path(tmp_dir.path())
+            .ok_or_else(|| anyhow!("Temporary directory is not valid UTF-8"))?;
+
+        copy_kitchensink(&kitchensink, copy)?;
+        let manifest = copy.join("Cargo.toml");
+        let rewritten = rewrite_workspace_dependencies(&kitchensink, &combination)?;
+        std::fs::write(&manifest, rewritten)
+            .with_context(|| format!("Failed to write {}", manifest))?;
+
+        let context = BuildRunner::for_manifest(copy.join(scenario.manifest()))
+            .arg("--bin")
+            .arg(scenario.bin)
+            .arg("--release")
+            .run()
+            .map_err(|e| anyhow!("Build with `{}` failed: {:?}", combination.name, e))?;
+
+        points.push(FeaturePoint {
+            combination,
+            wall_duration: context.wall_duration,
+            file_size: context.file_size.value(),
+            text_size: context.text_size.value(),
+            llvm_lines: context.num_llvm_lines(),
+            crates: context.dep_crates.len(),
+        });
+    }
+
+    Ok(points)
+}
+
+/// The kitchensink manifest with the combination applied to its workspace
+/// dependencies, and the paths of those outside the kitchensink made absolute
+/// so it can be moved elsewhere. Whatever else their inline tables hold, such
+/// as a `version` or features already enabled, is kept.
+fn rewrite_workspace_dependencies(
+    kitchensink: &Utf8Path,
+    combination: &FeatureCombination,
+) -> Result<String> {
+    let mut manifest = KitchensinkManifest::read(kitchensink)?;
+    // Members such as ks-types must resolve to the copy, which is the
+    // workspace they inherit their own dependencies from
+    let kitchensink = kitchensink.canonicalize_utf8()?;
+
+    for dep in &mut manifest.dependencies {
+        if let Some(path) = dep.path() {
+            let resolved = kitchensink
+                .join(path)
+                .canonicalize_utf8()
+                .with_context(|| format!("Dependency `{}` not found at {}", dep.name, path))?;
+            let moved = resolved.strip_prefix(&kitchensink).unwrap_or(&resolved);
+            if moved != path {
+                dep.set("path", format!("\"{}\"", moved));
+            }
+        }
+        if combination.no_default.contains(&dep.name) {
+            dep.set("default-features", "false".to_string());
+        }
+        if let Some(features) = combination.features.get(&dep.name) {
+            dep.add_features(features);
+        }
+    }
+
+    Ok(manifest.render())
+}
@@ -101,9 +101,43 @@ pub fn remove_worktree(repo_path: &Utf8PathBuf, worktree_path: &Utf8PathBuf) ->
     Ok(())
 }
 
-/// Create a comparison workspace with both facet and limpid worktrees
+/// Branch the facet baseline is taken from: the target branch of the pull
+/// request when running in GitHub Actions, `origin/main` otherwise
+pub fn default_target_branch() -> String {
+    match std::env::var("GITHUB_BASE_REF") {
+        Ok(base_ref) if !base_ref.is_empty() => format!("origin/{}", base_ref),
+        _ => "origin/main".to_string(),
+    }
+}
+
+/// The commit where HEAD of `repo` branched off `target`, so that comparing
+/// to it leaves out whatever was merged into `target` since
+pub fn merge_base(repo: &Utf8Path, target: &str) -> Result<String> {
+    let mut cmd = Command::new("git");
+    cmd.args(["merge-base", "HEAD", target]).current_dir(repo);
+    let output = run_command(&mut cmd)?;
+
+    ensure!(
+        output.status.success(),
+        "No merge-base between HEAD and {} in {}: {}\n\
+         In a shallow clone, fetch the full history of both (e.g. `fetch-depth: 0` \
+         with actions/checkout).",
+        target,
+        repo,
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+
+    Ok(std::str::from_utf8(&output.stdout)
+        .context("Invalid UTF-8 in git output")?
+        .trim()
+        .to_string())
+}
+
+/// Create a comparison workspace with a facet worktree at `facet_baseline`
+/// and a limpid worktree at HEAD
 pub fn create_comparison_workspace(
     facet_repo: &Utf8PathBuf,
+    facet_baseline: &str,
     limpid_repo: &Utf8PathBuf,
     workspace_dir: &Utf8PathBuf,
 ) -> Result<(Utf8PathBuf, Utf8PathBuf)> {
@@ -118,13 +152,14 @@ pub fn create_comparison_workspace(
         workspace_dir.bright_blue()
     );
 
-    // Create facet worktree at main branch
+    // Create facet worktree at the baseline
     let facet_worktree = workspace_dir.join("facet");
     println!(
-        "\n  {} Creating facet worktree at main branch...",
-        "1️⃣ ".bright_black()
+        "\n  {} Creating facet worktree at {}...",
+        "1️⃣ ".bright_black(),
+        facet_baseline.yellow()
     );
-    create_worktree(facet_repo, &facet_worktree, "origin/main")?;
+    create_worktree(facet_repo, &facet_worktree, facet_baseline)?;
 
     // Get current HEAD of limpid for the worktree
     let mut cmd = Command::new("git");
@@ -3,21 +3,74 @@ use camino::{Utf8Path, Utf8PathBuf};
 use owo_colors::OwoColorize;
 use substance::{BuildContext, BuildRunner};
 
+mod allocs;
+mod bench;
+mod build_env;
+mod categories;
 mod cli;
+mod correctness;
+mod dependencies;
+mod derive_only;
 mod facet_specific;
+mod feature_matrix;
 mod git;
+mod malformed;
+mod memory;
+mod provenance;
 mod report;
+mod scaling;
+mod severity;
+mod toolchain;
+mod workspace_deps;
 
 use cli::CliConfig;
-use facet_specific::{find_facet_workspace, verify_kitchensink_structure};
-use git::{create_comparison_workspace, find_git_root, remove_worktree};
+use facet_specific::{
+    find_facet_workspace, redirect_facet_path_deps, verify_kitchensink_structure, KITCHENSINK_PATH,
+    KS_FACET_BIN, KS_FACET_DERIVE_BIN, KS_FACET_ERRORS_BIN, KS_FACET_ERRORS_MANIFEST,
+    KS_FACET_MANIFEST, KS_SERDE_BIN, KS_SERDE_ERRORS_BIN, KS_SERDE_ERRORS_MANIFEST,
+    KS_SERDE_MANIFEST,
+};
+use git::{create_comparison_workspace, find_git_root, merge_base, remove_worktree};
 
-use crate::report::generate_reports;
+use crate::allocs::run_alloc_count;
+use crate::bench::run_benchmark;
+use crate::build_env::sanitize_build_env;
+use crate::categories::{parse_category_rules, CATEGORIES_PATH};
+use crate::correctness::run_correctness_check;
+use crate::dependencies::resolve_dependency_graph;
+use crate::derive_only::run_derive_only_check;
+use crate::feature_matrix::run_feature_matrix;
+use crate::malformed::run_malformed_corpus;
+use crate::memory::{rustc_wrapper_main, RustcMemory, RustcMemoryProbe, MEMORY_LOG_ENV};
+use crate::provenance::collect_provenance;
+use crate::report::{
+    generate_alloc_report, generate_bench_report, generate_category_report,
+    generate_correctness_report, generate_dependency_report, generate_derive_only_report,
+    generate_feature_matrix_report, generate_function_cost_report, generate_malformed_report,
+    generate_memory_report, generate_reports, generate_scaling_report,
+};
+use crate::scaling::run_scaling;
+use crate::toolchain::with_toolchain;
+
+/// Everything measured while building one side of the comparison
+struct BuildAnalysis {
+    context: BuildContext,
+    /// Peak rustc memory usage per crate, if requested
+    rustc_memory: Option<RustcMemory>,
+}
 
 fn main() -> Result<()> {
+    // When measuring rustc memory usage, cargo invokes us as the rustc wrapper
+    if let Some(log_path) = std::env::var_os(MEMORY_LOG_ENV) {
+        std::process::exit(rustc_wrapper_main(log_path));
+    }
+
     let config = CliConfig::from_args()?;
     config.init_logging();
 
+    // Before anything runs cargo, so every build sees the same environment
+    sanitize_build_env(&config.build_env)?;
+
     let current_dir = Utf8PathBuf::from_path_buf(std::env::current_dir()?)
         .map_err(|_| anyhow::anyhow!("Current directory is not valid UTF-8"))?;
 
@@ -28,12 +81,29 @@ fn main() -> Result<()> {
     println!("🌳 limpid repo root: {}", limpid_root.green());
 
     // Verify kitchensink structure
-    let _ks_facet_manifest = verify_kitchensink_structure(&limpid_root)?;
+    let _scenario_manifest = verify_kitchensink_structure(&limpid_root, &config.scenario)?;
+
+    // Both builds are categorized with the current rules, so they compare like for like
+    let category_rules = parse_category_rules(
+        &config
+            .categories
+            .clone()
+            .unwrap_or_else(|| limpid_root.join(KITCHENSINK_PATH).join(CATEGORIES_PATH)),
+    )?;
 
     // Find the facet repository
-    let facet_root = find_facet_workspace(&limpid_root)?;
+    let facet_root = find_facet_workspace(&limpid_root, config.facet_path.as_deref())?;
     println!("🌊 facet repo root: {}", facet_root.green());
 
+    // Builds of the current side go through a redirected copy of the
+    // kitchensink if its path dependencies lead to another facet checkout
+    let current_redirect = redirect_facet_path_deps(&limpid_root, &facet_root)?;
+    let current_root = match &current_redirect {
+        Some(dir) => Utf8PathBuf::from_path_buf(dir.path().to_path_buf())
+            .expect("temp dir should be valid UTF-8"),
+        None => limpid_root.clone(),
+    };
+
     // Create a temporary workspace for comparison
     let tmp_dir = if let Ok(env_tmp) = std::env::var("SUBSTANCE_TMP_DIR") {
         println!(
@@ -52,23 +122,275 @@ fn main() -> Result<()> {
     let workspace_dir = Utf8PathBuf::from_path_buf(tmp_dir.join("limpid-workspace"))
         .expect("temp dir should be valid UTF-8");
 
+    let toolchains = config.toolchains.as_ref();
+    let baseline_toolchain = toolchains.map(|(baseline, _)| baseline);
+    let current_toolchain = toolchains.map(|(_, current)| current);
+
     // Create comparison workspace — this creates worktrees of facet and limpid as sibling
-    // directories into the temporary workspace directory.
-    let (facet_worktree, limpid_worktree) =
-        create_comparison_workspace(&facet_root, &limpid_root, &workspace_dir)?;
+    // directories into the temporary workspace directory. Comparing toolchains builds the
+    // current checkouts on both sides instead.
+    let (facet_worktree, limpid_worktree, facet_baseline_ref) = if toolchains.is_some() {
+        (facet_root.clone(), current_root.clone(), None)
+    } else {
+        let (facet_baseline, baseline_ref) = if config.merge_base {
+            let base = merge_base(&facet_root, &config.target_branch)?;
+            println!(
+                "🔀 Baseline: merge-base of HEAD and {} ({})",
+                config.target_branch.green(),
+                (&base[..8]).yellow()
+            );
+            let baseline_ref = format!("merge-base of HEAD and {}", config.target_branch);
+            (base, baseline_ref)
+        } else {
+            (config.target_branch.clone(), config.target_branch.clone())
+        };
+        let (facet_worktree, limpid_worktree) = create_comparison_workspace(
+            &facet_root,
+            &facet_baseline,
+            &limpid_root,
+            &workspace_dir,
+        )?;
+        (facet_worktree, limpid_worktree, Some(baseline_ref))
+    };
+
+    // Same for the baseline, whose facet worktree is always `facet`
+    let baseline_redirect = if toolchains.is_some() {
+        None
+    } else {
+        redirect_facet_path_deps(&limpid_worktree, &facet_worktree)?
+    };
+    let baseline_root = match &baseline_redirect {
+        Some(dir) => Utf8PathBuf::from_path_buf(dir.path().to_path_buf())
+            .expect("temp dir should be valid UTF-8"),
+        None => limpid_worktree.clone(),
+    };
+
+    let provenance = collect_provenance(
+        &facet_worktree,
+        facet_baseline_ref,
+        &facet_root,
+        &limpid_root,
+        &limpid_root.join(KITCHENSINK_PATH).join("Cargo.toml"),
+        toolchains,
+        config.size_only,
+    )?;
 
     // Perform comparison analysis
-    let (baseline, current) = perform_comparison_analysis(&limpid_worktree, &limpid_root)?;
+    let (baseline, current) = perform_comparison_analysis(&config, &baseline_root, &current_root)?;
+
+    let scenario_manifest = config.scenario.manifest();
+    let dependency_graphs = (
+        with_toolchain(baseline_toolchain, || {
+            resolve_dependency_graph(
+                &baseline_root
+                    .join(KITCHENSINK_PATH)
+                    .join(&scenario_manifest),
+            )
+        })?,
+        with_toolchain(current_toolchain, || {
+            resolve_dependency_graph(&current_root.join(KITCHENSINK_PATH).join(&scenario_manifest))
+        })?,
+    );
+
+    let correctness_results = if config.verify {
+        Some((
+            with_toolchain(baseline_toolchain, || run_correctness_check(&baseline_root))?,
+            with_toolchain(current_toolchain, || run_correctness_check(&current_root))?,
+        ))
+    } else {
+        None
+    };
+
+    let derive_only_results = if config.scenario.bin == KS_FACET_DERIVE_BIN {
+        Some((
+            with_toolchain(baseline_toolchain, || run_derive_only_check(&baseline_root))?,
+            with_toolchain(current_toolchain, || run_derive_only_check(&current_root))?,
+        ))
+    } else {
+        None
+    };
+
+    // As for allocations, serde_json only runs once, as a reference point
+    let malformed_results = if config.scenario.bin == KS_FACET_ERRORS_BIN {
+        Some((
+            with_toolchain(baseline_toolchain, || {
+                run_malformed_corpus(
+                    &baseline_root,
+                    KS_FACET_ERRORS_MANIFEST,
+                    KS_FACET_ERRORS_BIN,
+                )
+            })?,
+            with_toolchain(current_toolchain, || {
+                run_malformed_corpus(&current_root, KS_FACET_ERRORS_MANIFEST, KS_FACET_ERRORS_BIN)
+            })?,
+            with_toolchain(current_toolchain, || {
+                run_malformed_corpus(&current_root, KS_SERDE_ERRORS_MANIFEST, KS_SERDE_ERRORS_BIN)
+            })?,
+        ))
+    } else {
+        None
+    };
+
+    // serde doesn't depend on facet, so it's only counted once, as a reference point
+    let alloc_results = if config.allocs {
+        Some((
+            with_toolchain(baseline_toolchain, || {
+                run_alloc_count(&baseline_root, KS_FACET_MANIFEST, KS_FACET_BIN)
+            })?,
+            with_toolchain(current_toolchain, || {
+                run_alloc_count(&current_root, KS_FACET_MANIFEST, KS_FACET_BIN)
+            })?,
+            with_toolchain(current_toolchain, || {
+                run_alloc_count(&current_root, KS_SERDE_MANIFEST, KS_SERDE_BIN)
+            })?,
+        ))
+    } else {
+        None
+    };
+
+    // Runtime benchmarks run after the build analysis so they don't skew build timings
+    let bench_results = match &config.bench {
+        Some(settings) => Some((
+            with_toolchain(baseline_toolchain, || {
+                run_benchmark(&baseline_root, settings)
+            })?,
+            with_toolchain(current_toolchain, || run_benchmark(&current_root, settings))?,
+        )),
+        None => None,
+    };
+
+    // The synthetic crates depend on facet directly, not through the kitchensink
+    let scaling_results = match &config.scaling {
+        Some(settings) => Some((
+            with_toolchain(baseline_toolchain, || {
+                run_scaling(&facet_worktree, settings)
+            })?,
+            with_toolchain(current_toolchain, || run_scaling(&facet_root, settings))?,
+        )),
+        None => None,
+    };
+
+    // Combinations are compared to each other, so only the current checkout is built
+    let feature_matrix_results = if config.feature_matrix {
+        Some(with_toolchain(current_toolchain, || {
+            run_feature_matrix(&current_root, &config.scenario)
+        })?)
+    } else {
+        None
+    };
 
     // Clean up worktrees
-    let _ = remove_worktree(&facet_root, &facet_worktree);
-    let _ = remove_worktree(&limpid_root, &limpid_worktree);
-    let _ = std::fs::remove_dir_all(&workspace_dir);
+    if toolchains.is_none() {
+        let _ = remove_worktree(&facet_root, &facet_worktree);
+        let _ = remove_worktree(&limpid_root, &limpid_worktree);
+        let _ = std::fs::remove_dir_all(&workspace_dir);
+    }
 
     let mut txt_output = String::new();
     let mut md_output = String::new();
 
-    generate_reports(&baseline, &current, &mut txt_output, &mut md_output)?;
+    generate_reports(
+        &config.scenario,
+        &baseline.context,
+        &current.context,
+        &config.severity,
+        &provenance,
+        &mut txt_output,
+        &mut md_output,
+    )?;
+
+    generate_function_cost_report(
+        &baseline.context,
+        &current.context,
+        &mut txt_output,
+        &mut md_output,
+    )?;
+
+    generate_category_report(
+        &category_rules,
+        &baseline.context,
+        &current.context,
+        &mut txt_output,
+        &mut md_output,
+    )?;
+
+    generate_dependency_report(
+        &dependency_graphs.0,
+        &dependency_graphs.1,
+        &mut txt_output,
+        &mut md_output,
+    )?;
+
+    if let (Some(baseline_memory), Some(current_memory)) =
+        (&baseline.rustc_memory, &current.rustc_memory)
+    {
+        generate_memory_report(
+            baseline_memory,
+            current_memory,
+            &mut txt_output,
+            &mut md_output,
+        )?;
+    }
+
+    if let Some((baseline_checks, current_checks)) = &correctness_results {
+        generate_correctness_report(
+            baseline_checks,
+            current_checks,
+            &mut txt_output,
+            &mut md_output,
+        )?;
+    }
+
+    if let Some((baseline_derive, current_derive)) = &derive_only_results {
+        generate_derive_only_report(
+            baseline_derive,
+            current_derive,
+            &mut txt_output,
+            &mut md_output,
+        )?;
+    }
+
+    if let Some((baseline_errors, current_errors, serde_errors)) = &malformed_results {
+        generate_malformed_report(
+            baseline_errors,
+            current_errors,
+            serde_errors,
+            &mut txt_output,
+            &mut md_output,
+        )?;
+    }
+
+    if let Some((baseline_allocs, current_allocs, serde_allocs)) = &alloc_results {
+        generate_alloc_report(
+            baseline_allocs,
+            current_allocs,
+            serde_allocs,
+            &mut txt_output,
+            &mut md_output,
+        )?;
+    }
+
+    if let Some((baseline_bench, current_bench)) = &bench_results {
+        generate_bench_report(
+            baseline_bench,
+            current_bench,
+            &mut txt_output,
+            &mut md_output,
+        )?;
+    }
+
+    if let Some((baseline_scaling, current_scaling)) = &scaling_results {
+        generate_scaling_report(
+            baseline_scaling,
+            current_scaling,
+            &mut txt_output,
+            &mut md_output,
+        )?;
+    }
+
+    if let Some(points) = &feature_matrix_results {
+        generate_feature_matrix_report(&config.scenario, points, &mut txt_output, &mut md_output)?;
+    }
 
     println!("{}", txt_output);
 
@@ -80,43 +402,89 @@ fn main() -> Result<()> {
         );
     }
 
+    // Unused types that the baseline dropped but the current build keeps are
+    // a regression: fail the run, once the report is out
+    if let Some((baseline_derive, current_derive)) = &derive_only_results {
+        let new_survivors: Vec<&str> = current_derive
+            .new_survivors(baseline_derive)
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        anyhow::ensure!(
+            new_survivors.is_empty(),
+            "Unused derived types survived into {} with the current build only: {}",
+            KS_FACET_DERIVE_BIN,
+            new_survivors.join(", ")
+        );
+    }
+
     Ok(())
 }
 
 /// Perform comparison analysis between baseline and current versions
 fn perform_comparison_analysis(
+    config: &CliConfig,
     limpid_baseline: &Utf8PathBuf,
     limpid_current: &Utf8PathBuf,
-) -> Result<(BuildContext, BuildContext)> {
+) -> Result<(BuildAnalysis, BuildAnalysis)> {
     let baseline_manifest = limpid_baseline
-        .join("kitchensink")
-        .join("ks-facet")
-        .join("Cargo.toml");
-    let baseline = build_and_analyze(&baseline_manifest)?;
-
+        .join(KITCHENSINK_PATH)
+        .join(config.scenario.manifest());
     let current_manifest = limpid_current
-        .join("kitchensink")
-        .join("ks-facet")
-        .join("Cargo.toml");
-    let current_context = build_and_analyze(&current_manifest)?;
+        .join(KITCHENSINK_PATH)
+        .join(config.scenario.manifest());
 
-    Ok((baseline, current_context))
+    // Sizes don't depend on what else the machine is doing, so both sides can
+    // build at once, each in its own target directory. Toolchains are selected
+    // through our own environment, so comparing them stays serial.
+    if config.size_only && config.toolchains.is_none() {
+        println!("⚡ Size only: building baseline and current concurrently");
+        return std::thread::scope(|s| {
+            let baseline = s.spawn(|| build_and_analyze(config, &baseline_manifest));
+            let current = build_and_analyze(config, &current_manifest);
+            let baseline = baseline
+                .join()
+                .map_err(|_| anyhow::anyhow!("Baseline build panicked"))??;
+            Ok((baseline, current?))
+        });
+    }
+
+    let baseline = with_toolchain(config.toolchains.as_ref().map(|(b, _)| b), || {
+        build_and_analyze(config, &baseline_manifest)
+    })?;
+
+    let current = with_toolchain(config.toolchains.as_ref().map(|(_, c)| c), || {
+        build_and_analyze(config, &current_manifest)
+    })?;
+
+    Ok((baseline, current))
 }
 
 /// Build and analyze a manifest
-fn build_and_analyze(manifest_path: &Utf8Path) -> Result<BuildContext> {
+fn build_and_analyze(config: &CliConfig, manifest_path: &Utf8Path) -> Result<BuildAnalysis> {
     // Create build runner with unique target directory
     let runner = BuildRunner::for_manifest(manifest_path)
         .arg("--bin")
-        .arg("ks-facet")
+        .arg(config.scenario.bin)
         .arg("--release");
 
     println!("📦 Building {}...", manifest_path.parent().unwrap());
 
+    let memory_probe = if config.rustc_memory {
+        Some(RustcMemoryProbe::install()?)
+    } else {
+        None
+    };
+
     // Run the build
     let context = runner
         .run()
         .map_err(|e| anyhow::anyhow!("Build failed: {:?}", e))?;
 
-    Ok(context)
+    let rustc_memory = memory_probe.map(|probe| probe.finish()).transpose()?;
+
+    Ok(BuildAnalysis {
+        context,
+        rustc_memory,
+    })
 }
@@ -0,0 +1,161 @@
+//! Error paths of the JSON readers, exercised with malformed input
+//!
+//! The `ks-facet-errors` and `ks-serde-errors` binaries feed the corpus of
+//! `ks_mock::malformed` to their reader and print every rendered error. This
+//! records whether each case was rejected and how useful the message is; the
+//! size of the binaries tells what error reporting costs.
+
+use anyhow::{anyhow, ensure, Context, Result};
+use camino::Utf8Path;
+use std::process::Command;
+
+use crate::facet_specific::{build_kitchensink_bin, text_size, KITCHENSINK_PATH};
+use crate::git::run_command;
+
+/// How a reader handled a malformed document
+#[derive(Debug, Clone)]
+pub enum CaseOutcome {
+    /// The document was deserialized anyway
+    Accepted,
+    Rejected(ErrorMessage),
+}
+
+/// A rendered error message, with ANSI colors stripped by the scenario binary
+#[derive(Debug, Clone)]
+pub struct ErrorMessage {
+    pub text: String,
+    /// Whether the message gives a line/column (or offset) into the input
+    pub has_location: bool,
+    /// Whether the message names the offending field or value, if the case has one
+    pub mentions_culprit: bool,
+}
+
+/// One document of the corpus
+#[derive(Debug, Clone)]
+pub struct MalformedCase {
+    pub name: String,
+    /// Field name or value a helpful message mentions
+    pub culprit: Option<String>,
+    pub outcome: CaseOutcome,
+}
+
+/// Results of running one error scenario binary
+#[derive(Debug, Clone)]
+pub struct MalformedResult {
+    /// Size of the executable's `.text` section
+    pub text_size: u64,
+    pub cases: Vec<MalformedCase>,
+}
+
+impl MalformedResult {
+    pub fn get(&self, name: &str) -> Option<&CaseOutcome> {
+        self.cases
+            .iter()
+            .find(|c| c.name == name)
+            .map(|c| &c.outcome)
+    }
+}
+
+/// Build and run an error scenario binary in the given limpid checkout.
+pub fn run_malformed_corpus(
+    limpid_root: &Utf8Path,
+    manifest: &str,
+    bin: &str,
+) -> Result<MalformedResult> {
+    println!(
+        "🚨 Feeding malformed JSON to {} in {}...",
+        bin,
+        limpid_root.join(KITCHENSINK_PATH)
+    );
+
+    let executable = build_kitchensink_bin(limpid_root, manifest, bin)?;
+    let text_size = text_size(&executable)?;
+
+    let output = run_command(&mut Command::new(executable.as_str()))?;
+    ensure!(
+        output.status.success(),
+        "{} failed: {}",
+        bin,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8(output.stdout)
+        .with_context(|| format!("Invalid UTF-8 in {} output", bin))?;
+
+    Ok(MalformedResult {
+        text_size,
+        cases: parse_case_lines(&stdout, bin)?,
+    })
+}
+
+/// Parse the `case` lines printed by `ks_mock::malformed::print_outcome`
+fn parse_case_lines(stdout: &str, bin: &str) -> Result<Vec<MalformedCase>> {
+    let mut cases = Vec::new();
+
+    for line in stdout.lines() {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let (name, culprit, outcome) = match fields.as_slice() {
+            ["case", name, culprit, "accepted"] => (name, culprit, None),
+            ["case", name, culprit, "rejected", message] => (name, culprit, Some(message)),
+            _ => continue,
+        };
+        let culprit = (*culprit != "-").then(|| culprit.to_string());
+
+        let outcome = match outcome {
+            None => CaseOutcome::Accepted,
+            Some(message) => {
+                let text = unescape(message);
+                CaseOutcome::Rejected(ErrorMessage {
+                    has_location: has_location(&text),
+                    mentions_culprit: culprit.as_deref().is_some_and(|c| text.contains(c)),
+                    text,
+                })
+            }
+        };
+
+        cases.push(MalformedCase {
+            name: name.to_string(),
+            culprit,
+            outcome,
+        });
+    }
+
+    if cases.is_empty() {
+        return Err(anyhow!("{} did not report any cases", bin));
+    }
+
+    Ok(cases)
+}
+
+/// Undo the escaping of tabs, newlines and backslashes
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Whether a message points into the input: serde's `line 3 column 5` or a
+/// `file:3:5`-style location
+fn has_location(message: &str) -> bool {
+    if message.contains(" line ") && message.contains(" column ") {
+        return true;
+    }
+    message.split(|c: char| c.is_whitespace()).any(|word| {
+        let parts: Vec<&str> = word.rsplitn(3, ':').collect();
+        parts.len() == 3
+            && parts[..2]
+                .iter()
+                .all(|p| !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit()))
+    })
+}
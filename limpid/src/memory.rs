@@ -0,0 +1,175 @@
+//! Peak rustc memory usage, measured through a `RUSTC_WRAPPER`
+//!
+//! During a measured build, limpid sets itself as the rustc wrapper. Each
+//! wrapper invocation runs rustc, reads the peak resident set size of its
+//! child process, and appends a `<crate name>\t<bytes>` line to a log file
+//! that the parent limpid process reads back once the build is done.
+
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::io::Write;
+use std::process::Command;
+
+/// Set (to the log file path) when limpid runs as a rustc wrapper
+pub const MEMORY_LOG_ENV: &str = "LIMPID_RUSTC_MEMORY_LOG";
+
+/// A `RUSTC_WRAPPER` that was configured before ours, which we chain to
+const INNER_WRAPPER_ENV: &str = "LIMPID_INNER_RUSTC_WRAPPER";
+
+/// Peak rustc memory usage for one build
+#[derive(Debug, Clone, Default)]
+pub struct RustcMemory {
+    /// Peak resident set size in bytes, per crate. If a crate was compiled
+    /// several times (e.g. for the host and the target), the largest is kept.
+    pub per_crate: BTreeMap<String, u64>,
+}
+
+impl RustcMemory {
+    /// The largest peak RSS of any rustc invocation
+    pub fn peak(&self) -> u64 {
+        self.per_crate.values().copied().max().unwrap_or(0)
+    }
+}
+
+/// Entry point when limpid is invoked by cargo as `limpid <rustc> <args...>`.
+///
+/// Returns the exit code of rustc.
+pub fn rustc_wrapper_main(log_path: OsString) -> i32 {
+    let mut args = std::env::args_os().skip(1);
+    let Some(rustc) = args.next() else {
+        eprintln!("limpid: rustc wrapper invoked without a rustc path");
+        return 1;
+    };
+    let args: Vec<OsString> = args.collect();
+
+    let mut cmd = match std::env::var_os(INNER_WRAPPER_ENV) {
+        Some(inner) if !inner.is_empty() => {
+            let mut cmd = Command::new(inner);
+            cmd.arg(rustc);
+            cmd
+        }
+        _ => Command::new(rustc),
+    };
+    cmd.args(&args).env_remove(MEMORY_LOG_ENV);
+
+    let status = match cmd.status() {
+        Ok(status) => status,
+        Err(e) => {
+            eprintln!("limpid: failed to run rustc: {}", e);
+            return 1;
+        }
+    };
+
+    // Queries like `rustc -vV` or `--print cfg` don't compile a crate
+    let crate_name = args
+        .iter()
+        .position(|a| a == "--crate-name")
+        .and_then(|i| args.get(i + 1))
+        .map(|name| name.to_string_lossy().into_owned());
+
+    if let (Some(crate_name), Some(peak)) = (crate_name, children_peak_rss()) {
+        let line = format!("{}\t{}\n", crate_name, peak);
+        let written = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_path)
+            .and_then(|mut f| f.write_all(line.as_bytes()));
+        if let Err(e) = written {
+            eprintln!("limpid: failed to record rustc memory usage: {}", e);
+        }
+    }
+
+    status.code().unwrap_or(1)
+}
+
+/// Peak resident set size (in bytes) of the largest terminated child process
+#[cfg(unix)]
+fn children_peak_rss() -> Option<u64> {
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    // SAFETY: `usage` is a valid, writable `rusage` struct
+    let ret = unsafe { libc::getrusage(libc::RUSAGE_CHILDREN, &mut usage) };
+    if ret != 0 {
+        return None;
+    }
+
+    let maxrss = usage.ru_maxrss as u64;
+    // `ru_maxrss` is in bytes on macOS, and in kilobytes everywhere else
+    if cfg!(target_os = "macos") {
+        Some(maxrss)
+    } else {
+        Some(maxrss * 1024)
+    }
+}
+
+#[cfg(not(unix))]
+fn children_peak_rss() -> Option<u64> {
+    None
+}
+
+/// Installs limpid as the rustc wrapper for the lifetime of the guard.
+///
+/// `BuildRunner` doesn't let us set environment variables on the cargo
+/// invocation, so this sets them on our own process and restores the previous
+/// values on drop.
+pub struct RustcMemoryProbe {
+    log_file: tempfile::NamedTempFile,
+    previous_wrapper: Option<OsString>,
+}
+
+impl RustcMemoryProbe {
+    pub fn install() -> Result<Self> {
+        if !cfg!(unix) {
+            eprintln!("⚠️  rustc memory usage can only be measured on Unix");
+        }
+
+        let log_file = tempfile::Builder::new()
+            .prefix("limpid-rustc-memory")
+            .tempfile()
+            .context("Failed to create rustc memory log")?;
+        let current_exe = std::env::current_exe().context("Failed to locate limpid binary")?;
+        let previous_wrapper = std::env::var_os("RUSTC_WRAPPER");
+
+        if let Some(previous) = &previous_wrapper {
+            std::env::set_var(INNER_WRAPPER_ENV, previous);
+        }
+        std::env::set_var("RUSTC_WRAPPER", current_exe);
+        std::env::set_var(MEMORY_LOG_ENV, log_file.path());
+
+        Ok(Self {
+            log_file,
+            previous_wrapper,
+        })
+    }
+
+    /// Uninstall the wrapper and collect what was recorded
+    pub fn finish(self) -> Result<RustcMemory> {
+        let contents = std::fs::read_to_string(self.log_file.path())
+            .context("Failed to read rustc memory log")?;
+
+        let mut memory = RustcMemory::default();
+        for line in contents.lines() {
+            let Some((crate_name, bytes)) = line.split_once('\t') else {
+                continue;
+            };
+            let Ok(bytes) = bytes.parse::<u64>() else {
+                continue;
+            };
+            let entry = memory.per_crate.entry(crate_name.to_string()).or_default();
+            *entry = (*entry).max(bytes);
+        }
+
+        Ok(memory)
+    }
+}
+
+impl Drop for RustcMemoryProbe {
+    fn drop(&mut self) {
+        std::env::remove_var(MEMORY_LOG_ENV);
+        std::env::remove_var(INNER_WRAPPER_ENV);
+        match &self.previous_wrapper {
+            Some(previous) => std::env::set_var("RUSTC_WRAPPER", previous),
+            None => std::env::remove_var("RUSTC_WRAPPER"),
+        }
+    }
+}
@@ -0,0 +1,259 @@
+//! What a report compared, and where it was produced
+//!
+//! A report pasted in an issue is only interpretable if it says which
+//! commits were compared, with which toolchain, on which machine and when.
+
+use anyhow::{ensure, Context, Result};
+use camino::Utf8Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::build_env::{effective_build_env, measured_rustflags};
+use crate::git::run_command;
+use crate::toolchain::Toolchain;
+
+/// A commit of one of the repositories involved
+#[derive(Debug, Clone)]
+pub struct CommitInfo {
+    pub sha: String,
+    pub subject: String,
+    /// Whether the checkout had uncommitted changes
+    pub dirty: bool,
+}
+
+impl CommitInfo {
+    /// e.g. `1a2b3c4d Fix the thing (+ uncommitted changes)`
+    pub fn describe(&self) -> String {
+        format!(
+            "{} {}{}",
+            &self.sha[..self.sha.len().min(8)],
+            self.subject,
+            if self.dirty {
+                " (+ uncommitted changes)"
+            } else {
+                ""
+            }
+        )
+    }
+}
+
+/// Everything needed to tell what a report compared
+#[derive(Debug, Clone)]
+pub struct Provenance {
+    pub facet_baseline: CommitInfo,
+    /// How the baseline commit was chosen, e.g. `origin/main`, unless comparing toolchains
+    pub facet_baseline_ref: Option<String>,
+    pub facet_current: CommitInfo,
+    /// The limpid checkout, which holds the kitchensink
+    pub limpid: CommitInfo,
+    /// `rustc --version`, or the versions of both toolchains when comparing them
+    pub rustc: String,
+    /// Names of the (baseline, current) toolchains, when comparing them
+    pub toolchains: Option<(String, String)>,
+    /// `cargo --version`, or the versions of both toolchains when comparing them
+    pub cargo: String,
+    /// Host triple, which the kitchensink is built for, or those of both
+    /// toolchains when comparing them
+    pub target: String,
+    /// Profile the scenario is built with, and its overrides
+    pub profile: String,
+    /// Whether only sizes were compared, leaving build times out of the report
+    pub size_only: bool,
+    /// Flags rustc gets in the measured build
+    pub rustflags: String,
+    /// Build environment variables left set after sanitizing, e.g. `CARGO_INCREMENTAL=0`
+    pub build_env: Vec<(String, String)>,
+    pub cpu: String,
+    pub cores: usize,
+    /// When the report was generated, in UTC
+    pub timestamp: String,
+}
+
+/// Gather the provenance of a comparison of `facet_baseline` to `facet_current`
+pub fn collect_provenance(
+    facet_baseline: &Utf8Path,
+    facet_baseline_ref: Option<String>,
+    facet_current: &Utf8Path,
+    limpid_root: &Utf8Path,
+    kitchensink_manifest: &Utf8Path,
+    toolchains: Option<&(Toolchain, Toolchain)>,
+    size_only: bool,
+) -> Result<Provenance> {
+    // Also checks that both toolchains are installed, before anything is built
+    let (rustc, cargo, target) = match toolchains {
+        Some((baseline, current)) => (
+            format!("{} → {}", baseline.version()?, current.version()?),
+            both(baseline.cargo_version()?, current.cargo_version()?),
+            both(baseline.host()?, current.host()?),
+        ),
+        None => {
+            let rustc_verbose = command_stdout(Command::new("rustc").arg("-vV"))?;
+            let target = rustc_verbose
+                .lines()
+                .find_map(|line| line.strip_prefix("host: "))
+                .unwrap_or("unknown")
+                .to_string();
+            (
+                rustc_verbose.lines().next().unwrap_or_default().to_string(),
+                command_stdout(Command::new("cargo").arg("--version"))?,
+                target,
+            )
+        }
+    };
+
+    Ok(Provenance {
+        facet_baseline: commit_info(facet_baseline)?,
+        facet_baseline_ref,
+        facet_current: commit_info(facet_current)?,
+        limpid: commit_info(limpid_root)?,
+        rustc,
+        toolchains: toolchains.map(|(b, c)| (b.to_string(), c.to_string())),
+        cargo,
+        target,
+        profile: release_profile(kitchensink_manifest)?,
+        size_only,
+        rustflags: measured_rustflags(),
+        build_env: effective_build_env(),
+        cpu: cpu_model(),
+        cores: std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1),
+        timestamp: utc_timestamp(SystemTime::now()),
+    })
+}
+
+/// `baseline → current`, or either one if they are the same
+fn both(baseline: String, current: String) -> String {
+    if baseline == current {
+        current
+    } else {
+        format!("{} → {}", baseline, current)
+    }
+}
+
+fn command_stdout(cmd: &mut Command) -> Result<String> {
+    let output = run_command(cmd)?;
+    ensure!(
+        output.status.success(),
+        "{:?} failed: {}",
+        cmd,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(String::from_utf8(output.stdout)
+        .context("Invalid UTF-8 in command output")?
+        .trim()
+        .to_string())
+}
+
+/// HEAD of the git checkout at `repo`
+fn commit_info(repo: &Utf8Path) -> Result<CommitInfo> {
+    let log = command_stdout(
+        Command::new("git")
+            .args(["log", "-1", "--format=%H%x09%s"])
+            .current_dir(repo),
+    )?;
+    let (sha, subject) = log.split_once('\t').unwrap_or((log.as_str(), ""));
+    let status = command_stdout(
+        Command::new("git")
+            .args(["status", "--porcelain", "--untracked-files=no"])
+            .current_dir(repo),
+    )?;
+
+    Ok(CommitInfo {
+        sha: sha.to_string(),
+        subject: subject.to_string(),
+        dirty: !status.is_empty(),
+    })
+}
+
+/// `release`, followed by the `[profile.release]` settings of the kitchensink
+/// manifest and the `CARGO_PROFILE_RELEASE_*` variables of the environment
+fn release_profile(manifest: &Utf8Path) -> Result<String> {
+    let source = std::fs::read_to_string(manifest)
+        .with_context(|| format!("Failed to read {}", manifest))?;
+
+    let mut settings: Vec<String> = source
+        .lines()
+        .map(str::trim)
+        .skip_while(|line| *line != "[profile.release]")
+        .skip(1)
+        .take_while(|line| !line.starts_with('['))
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect();
+    settings.extend(
+        std::env::vars()
+            .filter(|(var, _)| var.starts_with("CARGO_PROFILE_RELEASE_"))
+            .map(|(var, value)| format!("{}={}", var, value)),
+    );
+
+    Ok(if settings.is_empty() {
+        "release (cargo defaults)".to_string()
+    } else {
+        format!("release ({})", settings.join(", "))
+    })
+}
+
+/// Model name of the first CPU, where the OS tells
+fn cpu_model() -> String {
+    std::fs::read_to_string("/proc/cpuinfo")
+        .ok()
+        .and_then(|info| {
+            info.lines()
+                .find_map(|line| line.strip_prefix("model name"))
+                .map(|rest| rest.trim_start_matches([' ', '\t', ':']).to_string())
+        })
+        .unwrap_or_else(|| std::env::consts::ARCH.to_string())
+}
+
+/// ISO 8601 timestamp, e.g. `2025-06-01T12:34:56Z`
+fn utc_timestamp(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::utc_timestamp;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn at(secs: u64) -> String {
+        utc_timestamp(UNIX_EPOCH + Duration::from_secs(secs))
+    }
+
+    #[test]
+    fn utc_timestamp_at_known_epochs() {
+        assert_eq!(at(0), "1970-01-01T00:00:00Z");
+        // Leap days, including one of a century divisible by 400
+        assert_eq!(at(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(at(1_709_210_096), "2024-02-29T12:34:56Z");
+        // Last second of a year, and of a leap year
+        assert_eq!(at(1_704_067_199), "2023-12-31T23:59:59Z");
+        assert_eq!(at(1_704_067_200), "2024-01-01T00:00:00Z");
+        assert_eq!(at(1_735_689_599), "2024-12-31T23:59:59Z");
+    }
+}
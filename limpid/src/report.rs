@@ -1,84 +1,103 @@
 use itertools::Itertools;
 use owo_colors::OwoColorize;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
 use std::{cmp, fmt::Write};
 use substance::{AggregateLlvmFunction, AggregateSymbol, BuildContext, ByteSize, CrateName};
 
+use crate::allocs::{AllocResult, AllocStats};
+use crate::bench::{BenchResult, BenchTiming};
+use crate::categories::{category_sizes, CategoryRules};
+use crate::correctness::{describe_check, CheckOutcome, CorrectnessResult};
+use crate::dependencies::{DependencyDiff, DependencyGraph};
+use crate::derive_only::DeriveOnlyResult;
+use crate::facet_specific::Scenario;
+use crate::feature_matrix::FeaturePoint;
+use crate::malformed::{CaseOutcome, MalformedResult};
+use crate::memory::RustcMemory;
+use crate::provenance::Provenance;
+use crate::scaling::ScalingPoint;
+use crate::severity::{percent_change, Finding, Severity, SeverityThresholds};
+
+/// Append to the text report, e.g. `tx!(tx_w, "{}\n", name.blue())`
+macro_rules! tx {
+    ($w:expr, $($arg:tt)*) => {
+        write!($w, $($arg)*).unwrap()
+    };
+}
+
+/// Append to the markdown report, e.g. `md!(md_w, "## {}\n\n", title)`
+macro_rules! md {
+    ($w:expr, $($arg:tt)*) => {
+        write!($w, $($arg)*).unwrap()
+    };
+}
+
 /// Generate a text (with colors) and a markdown report comparing two builds
 pub(crate) fn generate_reports(
+    scenario: &Scenario,
     baseline: &BuildContext,
     current: &BuildContext,
-    tx_w: &mut String,
-    md_w: &mut String,
+    thresholds: &SeverityThresholds,
+    provenance: &Provenance,
+    tx_out: &mut String,
+    md_out: &mut String,
 ) -> anyhow::Result<()> {
-    macro_rules! tx {
-        ($($arg:tt)*) => {
-            write!(tx_w, $($arg)*).unwrap();
-        };
-    }
-    macro_rules! md {
-        ($($arg:tt)*) => {
-            write!(md_w, $($arg)*).unwrap();
-        };
+    // The summary of findings goes at the top, so the body is written first
+    let mut tx_body = String::new();
+    let mut md_body = String::new();
+    let tx_w = &mut tx_body;
+    let md_w = &mut md_body;
+    let mut findings: Vec<Finding> = Vec::new();
+
+    macro_rules! total_diff {
+        ($what:expr, $old:expr, $new:expr, $fmt:expr) => {{
+            let (old, new) = ($old as u64, $new as u64);
+            let cell = change_cell(
+                || $what.to_owned(),
+                old,
+                new,
+                $fmt,
+                thresholds,
+                &mut findings,
+            );
+            let cell_tx = format!(" ({})", cell);
+            match new.cmp(&old) {
+                cmp::Ordering::Greater => {
+                    tx!(tx_w, "{}", cell_tx.green());
+                }
+                cmp::Ordering::Less => {
+                    tx!(tx_w, "{}", cell_tx.red());
+                }
+                cmp::Ordering::Equal => {
+                    tx!(tx_w, "{}", cell_tx.dimmed());
+                }
+            }
+            md!(md_w, " ({})", cell);
+        }};
     }
 
     macro_rules! bytes_diff {
-        ($old:expr, $new:expr) => {{
-            let diff = $new as isize - $old as isize;
-            use owo_colors::OwoColorize;
-            if diff > 0 {
-                tx!(
-                    "{}",
-                    format!(" (📈 +{})", format_bytes(diff as u64)).green()
-                );
-                md!(" (📈 +{})", format_bytes(diff as u64));
-            } else if diff < 0 {
-                tx!(
-                    "{}",
-                    format!(" (📉 -{})", format_bytes((-diff) as u64)).red()
-                );
-                md!(" (📉 -{})", format_bytes((-diff) as u64));
-            } else {
-                tx!("{}", " (➖ no change)".dimmed());
-                md!(" (➖ no change)");
-            }
-        }};
+        ($what:expr, $old:expr, $new:expr) => {
+            total_diff!($what, $old, $new, format_bytes)
+        };
     }
 
     macro_rules! unitless_diff {
-        ($old:expr, $new:expr) => {{
-            let diff = $new - $old;
-            if diff > 0 {
-                tx!(
-                    "{}",
-                    format!(" (📈 +{})", fmt_thousands(diff as isize)).green()
-                );
-                md!(" (📈 +{})", fmt_thousands(diff as isize));
-            } else if diff < 0 {
-                tx!(
-                    "{}",
-                    format!(" (📉 {})", fmt_thousands(diff as isize)).red()
-                );
-                md!(" (📉 {})", fmt_thousands(diff as isize));
-            } else {
-                tx!("{}", " (➖ no change)".dimmed());
-                md!(" (➖ no change)");
-            }
-        }};
+        ($what:expr, $old:expr, $new:expr) => {
+            total_diff!($what, $old, $new, |n| fmt_thousands(n as isize))
+        };
     }
 
-    tx!("{}", "limpid report\n".bright_blue());
-    md!("# 📦 limpid report\n\n");
-
     // Number of crates
     let current_num_crates = current.crates.len();
     let baseline_num_crates = baseline.crates.len();
 
-    tx!("Number of crates: {}", current_num_crates.blue());
-    md!("Number of crates: {}", current_num_crates);
-    unitless_diff!(baseline_num_crates as isize, current_num_crates as isize);
-    tx!("\n");
-    md!("  \n");
+    tx!(tx_w, "Number of crates: {}", current_num_crates.blue());
+    md!(md_w, "Number of crates: {}", current_num_crates);
+    unitless_diff!("Number of crates", baseline_num_crates, current_num_crates);
+    tx!(tx_w, "\n");
+    md!(md_w, "  \n");
 
     struct CrateWithSize {
         name: CrateName,
@@ -145,15 +164,15 @@ pub(crate) fn generate_reports(
         .collect();
 
     // Sort by absolute byte difference (largest first)
-    comparative_crates.sort_by_key(|c| cmp::Reverse(c.diff.abs() as u64));
+    comparative_crates.sort_by_key(|c| cmp::Reverse(c.diff.unsigned_abs()));
 
     // Split into detailed (top 10) and excluded crates
     let detailed_crates: Vec<&ComparativeCrate> = comparative_crates.iter().take(10).collect();
     let excluded_crates: Vec<&ComparativeCrate> = comparative_crates.iter().skip(10).collect();
 
     if !detailed_crates.is_empty() {
-        md!("| Crate | Baseline Size | Current Size | Change |\n");
-        md!("|-------|---------------|--------------|--------|\n");
+        md!(md_w, "| Crate | Baseline Size | Current Size | Change |\n");
+        md!(md_w, "|-------|---------------|--------------|--------|\n");
 
         for c in &detailed_crates {
             let baseline_fmt = c
@@ -165,19 +184,30 @@ pub(crate) fn generate_reports(
                 .map(|sz| format_bytes(sz.value()))
                 .unwrap_or_else(|| "—".to_string());
 
-            let change_str = if c.old.is_some() && c.new.is_some() {
-                if c.diff > 0 {
-                    format!("📈 +{}", format_bytes(c.diff as u64))
-                } else {
-                    format!("📉 -{}", format_bytes((-c.diff) as u64))
-                }
-            } else if c.old.is_none() && c.new.is_some() {
-                "🆕 NEW".to_owned()
+            let change_str = if let (Some(old), Some(new)) = (c.old, c.new) {
+                change_cell(
+                    || format!("crate `{}` size", c.name),
+                    old.value(),
+                    new.value(),
+                    format_bytes,
+                    thresholds,
+                    &mut findings,
+                )
+            } else if let Some(new) = c.new {
+                new_cell(
+                    || format!("crate `{}` size", c.name),
+                    new.value(),
+                    baseline.text_size.value(),
+                    format_bytes,
+                    thresholds,
+                    &mut findings,
+                )
             } else {
                 "🗑️ REMOVED".to_owned()
             };
 
             md!(
+                md_w,
                 "| `{}` | {} | {} | {} |\n",
                 c.name,
                 baseline_fmt,
@@ -196,16 +226,17 @@ pub(crate) fn generate_reports(
                 .iter()
                 .map(|c| c.new.map(|s| s.value()).unwrap_or(0))
                 .sum();
-            let diff = current_sum as isize - baseline_sum as isize;
-            let change_str = if diff > 0 {
-                format!("📈 +{}", format_bytes(diff as u64))
-            } else if diff < 0 {
-                format!("📉 -{}", format_bytes((-diff) as u64))
-            } else {
-                "➖ no change".to_owned()
-            };
+            let change_str = change_cell(
+                || format!("{} additional crates size", excluded_crates.len()),
+                baseline_sum,
+                current_sum,
+                format_bytes,
+                thresholds,
+                &mut findings,
+            );
 
             md!(
+                md_w,
                 "\n*{} additional crates account for **{}** → **{}** ({})*\n",
                 excluded_crates.len(),
                 format_bytes(baseline_sum),
@@ -213,29 +244,42 @@ pub(crate) fn generate_reports(
                 change_str
             );
         } else {
-            md!("\n_All significant changes are listed above._\n");
+            md!(md_w, "\n_All significant changes are listed above._\n");
         }
 
-        md!("\n");
+        md!(md_w, "\n");
     }
 
     // Number of symbols and .text section size on a single line
     let current_num_symbols = current.deps_symbols.len();
     let baseline_num_symbols = baseline.deps_symbols.len();
 
-    tx!("{} symbols", current_num_symbols.blue());
-    md!("{} symbols", current_num_symbols);
-    unitless_diff!(baseline_num_symbols as isize, current_num_symbols as isize);
+    tx!(tx_w, "{} symbols", current_num_symbols.blue());
+    md!(md_w, "{} symbols", current_num_symbols);
+    unitless_diff!(
+        "Number of symbols",
+        baseline_num_symbols,
+        current_num_symbols
+    );
 
     tx!(
+        tx_w,
         ", totaling {}",
         format_bytes(current.text_size.value()).cyan()
     );
-    md!(", totaling {}", format_bytes(current.text_size.value()));
-    bytes_diff!(baseline.text_size.value(), current.text_size.value());
+    md!(
+        md_w,
+        ", totaling {}",
+        format_bytes(current.text_size.value())
+    );
+    bytes_diff!(
+        "`.text` size",
+        baseline.text_size.value(),
+        current.text_size.value()
+    );
 
-    tx!("\n");
-    md!("  \n");
+    tx!(tx_w, "\n");
+    md!(md_w, "  \n");
 
     // Now let's select interesting symbols: any in the top 20 largest symbols in baseline or in current.
     // Then we'll assign them a rank in baseline and a rank in current.
@@ -264,7 +308,6 @@ pub(crate) fn generate_reports(
     }
 
     // Merge the top symbols from both baseline and current by name, deduped.
-    use std::collections::BTreeSet;
     let mut symbol_names: BTreeSet<&str> = BTreeSet::new();
     for sym in top_baseline.iter().chain(top_current.iter()) {
         symbol_names.insert(sym.name.as_str());
@@ -293,7 +336,7 @@ pub(crate) fn generate_reports(
         .iter()
         .filter(|sym| sym.size_diff != 0) // ignore symbols with no change
         .collect();
-    sorted_syms.sort_by_key(|sym| cmp::Reverse(sym.size_diff.abs() as u64));
+    sorted_syms.sort_by_key(|sym| cmp::Reverse(sym.size_diff.unsigned_abs()));
 
     // Take at most the top N entries for the detailed list and partition the rest
     const TOP_N_SYMBOLS: usize = 20;
@@ -306,8 +349,8 @@ pub(crate) fn generate_reports(
     // If there are any symbol size changes, render a detailed Markdown table
     if !detailed_syms.is_empty() {
         // Markdown table with explicit old/new/diff columns
-        md!("| Symbol | Baseline Size | Current Size | Change |\n");
-        md!("|--------|---------------|--------------|--------|\n");
+        md!(md_w, "| Symbol | Baseline Size | Current Size | Change |\n");
+        md!(md_w, "|--------|---------------|--------------|--------|\n");
 
         for sym in detailed_syms.iter() {
             let name = sym
@@ -352,17 +395,24 @@ pub(crate) fn generate_reports(
                 .unwrap_or_else(|| "—".to_string());
 
             // Diff string (re-use existing emoji style)
-            let diff = new_sz as isize - old_sz as isize;
             let change_str = if baseline_size.is_some() && current_size.is_some() {
-                if diff > 0 {
-                    format!("📈 +{}", format_bytes(diff as u64))
-                } else if diff < 0 {
-                    format!("📉 -{}", format_bytes((-diff) as u64))
-                } else {
-                    "➖ no change".to_owned()
-                }
+                change_cell(
+                    || format!("symbol `{}` size", name),
+                    old_sz,
+                    new_sz,
+                    format_bytes,
+                    thresholds,
+                    &mut findings,
+                )
             } else if baseline_size.is_none() && current_size.is_some() {
-                "🆕 NEW".to_owned()
+                new_cell(
+                    || format!("symbol `{}` size", name),
+                    new_sz,
+                    baseline.text_size.value(),
+                    format_bytes,
+                    thresholds,
+                    &mut findings,
+                )
             } else if baseline_size.is_some() && current_size.is_none() {
                 "🗑️ REMOVED".to_owned()
             } else {
@@ -371,6 +421,7 @@ pub(crate) fn generate_reports(
 
             // Final row
             md!(
+                md_w,
                 "| `{}`{} | {} | {} | {} |\n",
                 name,
                 crates_cell,
@@ -389,16 +440,17 @@ pub(crate) fn generate_reports(
         }
 
         if !excluded_syms.is_empty() {
-            let diff_excluded = current_sum_excluded as isize - baseline_sum_excluded as isize;
-            let change_excluded_str = if diff_excluded > 0 {
-                format!("📈 +{}", format_bytes(diff_excluded as u64))
-            } else if diff_excluded < 0 {
-                format!("📉 -{}", format_bytes((-diff_excluded) as u64))
-            } else {
-                "➖ no change".to_owned()
-            };
+            let change_excluded_str = change_cell(
+                || format!("{} additional symbols size", excluded_syms.len()),
+                baseline_sum_excluded,
+                current_sum_excluded,
+                format_bytes,
+                thresholds,
+                &mut findings,
+            );
 
             md!(
+                md_w,
                 "\n*{} additional symbols account for **{}** → **{}** ({})*\n",
                 excluded_syms.len(),
                 format_bytes(baseline_sum_excluded),
@@ -406,28 +458,198 @@ pub(crate) fn generate_reports(
                 change_excluded_str
             );
         } else {
-            md!("\n_All significant changes are listed above._\n");
+            md!(md_w, "\n_All significant changes are listed above._\n");
         }
 
-        md!("\n");
+        md!(md_w, "\n");
     }
-    md!("\n");
+    md!(md_w, "\n");
 
     // Number of LLVM IR lines
     let current_llvm_lines = current.num_llvm_lines();
     let baseline_llvm_lines = baseline.num_llvm_lines();
 
     tx!(
+        tx_w,
         "Number of LLVM lines: {}",
         fmt_thousands(current_llvm_lines as isize).blue()
     );
     md!(
+        md_w,
         "Number of LLVM lines: {}",
         fmt_thousands(current_llvm_lines as isize)
     );
-    unitless_diff!(baseline_llvm_lines as isize, current_llvm_lines as isize);
-    tx!("\n");
-    md!("  \n");
+    unitless_diff!(
+        "Number of LLVM lines",
+        baseline_llvm_lines,
+        current_llvm_lines
+    );
+    tx!(tx_w, "\n");
+    md!(md_w, "  \n");
+
+    // ── Per-crate LLVM IR line changes ────────────────────────────────────────
+
+    /// LLVM IR lines of a crate, and the number of function copies they come from
+    #[derive(Clone, Copy)]
+    struct CrateLlvm {
+        lines: usize,
+        copies: usize,
+    }
+
+    fn crate_llvm_map(context: &BuildContext) -> BTreeMap<&str, CrateLlvm> {
+        context
+            .crates
+            .iter()
+            .map(|krate| {
+                let copies = krate
+                    .llvm_functions
+                    .values()
+                    .map(|f| f.copies.value())
+                    .sum();
+                (
+                    krate.name.as_str(),
+                    CrateLlvm {
+                        lines: krate.num_llvm_lines(),
+                        copies,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    let current_llvm_map = crate_llvm_map(current);
+    let baseline_llvm_map = crate_llvm_map(baseline);
+
+    struct ComparativeCrateLlvm<'a> {
+        name: &'a str,
+        old: Option<CrateLlvm>,
+        new: Option<CrateLlvm>,
+        diff: isize,
+    }
+
+    let mut comparative_llvm_crates: Vec<ComparativeCrateLlvm> = current_llvm_map
+        .keys()
+        .chain(baseline_llvm_map.keys())
+        .copied()
+        .collect::<BTreeSet<&str>>()
+        .into_iter()
+        .map(|name| {
+            let old = baseline_llvm_map.get(name).copied();
+            let new = current_llvm_map.get(name).copied();
+            let old_lines = old.map(|c| c.lines).unwrap_or(0);
+            let new_lines = new.map(|c| c.lines).unwrap_or(0);
+            ComparativeCrateLlvm {
+                name,
+                old,
+                new,
+                diff: new_lines as isize - old_lines as isize,
+            }
+        })
+        // keep only crates whose IR changed, in size or in number of copies
+        .filter(|c| c.diff != 0 || c.old.map(|o| o.copies) != c.new.map(|n| n.copies))
+        .collect();
+
+    // Sort by absolute line difference (largest first)
+    comparative_llvm_crates.sort_by_key(|c| cmp::Reverse(c.diff.unsigned_abs()));
+
+    // Split into detailed (top 10) and excluded crates
+    let detailed_llvm_crates: Vec<&ComparativeCrateLlvm> =
+        comparative_llvm_crates.iter().take(10).collect();
+    let excluded_llvm_crates: Vec<&ComparativeCrateLlvm> =
+        comparative_llvm_crates.iter().skip(10).collect();
+
+    if !detailed_llvm_crates.is_empty() {
+        md!(
+            md_w,
+            "| Crate | Baseline LLVM Lines | Current LLVM Lines | Change | Copies |\n"
+        );
+        md!(
+            md_w,
+            "|-------|---------------------|--------------------|--------|--------|\n"
+        );
+
+        for c in &detailed_llvm_crates {
+            let fmt_lines = |side: Option<CrateLlvm>| {
+                side.map(|s| fmt_thousands(s.lines as isize))
+                    .unwrap_or_else(|| "—".to_string())
+            };
+            let fmt_copies = |side: Option<CrateLlvm>| {
+                side.map(|s| fmt_thousands(s.copies as isize))
+                    .unwrap_or_else(|| "—".to_string())
+            };
+
+            let change_str = if let (Some(old), Some(new)) = (c.old, c.new) {
+                change_cell(
+                    || format!("crate `{}` LLVM lines", c.name),
+                    old.lines as u64,
+                    new.lines as u64,
+                    |n| fmt_thousands(n as isize),
+                    thresholds,
+                    &mut findings,
+                )
+            } else if let Some(new) = c.new {
+                new_cell(
+                    || format!("crate `{}` LLVM lines", c.name),
+                    new.lines as u64,
+                    baseline.num_llvm_lines() as u64,
+                    |n| fmt_thousands(n as isize),
+                    thresholds,
+                    &mut findings,
+                )
+            } else {
+                "🗑️ REMOVED".to_owned()
+            };
+
+            md!(
+                md_w,
+                "| `{}` | {} | {} | {} | {} → {} |\n",
+                c.name,
+                fmt_lines(c.old),
+                fmt_lines(c.new),
+                change_str,
+                fmt_copies(c.old),
+                fmt_copies(c.new)
+            );
+        }
+
+        // Summarize excluded crates (those beyond the top list)
+        if !excluded_llvm_crates.is_empty() {
+            let baseline_sum: usize = excluded_llvm_crates
+                .iter()
+                .map(|c| c.old.map(|s| s.lines).unwrap_or(0))
+                .sum();
+            let current_sum: usize = excluded_llvm_crates
+                .iter()
+                .map(|c| c.new.map(|s| s.lines).unwrap_or(0))
+                .sum();
+            let change_str = change_cell(
+                || {
+                    format!(
+                        "{} additional crates LLVM lines",
+                        excluded_llvm_crates.len()
+                    )
+                },
+                baseline_sum as u64,
+                current_sum as u64,
+                |n| fmt_thousands(n as isize),
+                thresholds,
+                &mut findings,
+            );
+
+            md!(
+                md_w,
+                "\n*{} additional crates account for **{}** → **{}** LLVM lines ({})*\n",
+                excluded_llvm_crates.len(),
+                fmt_thousands(baseline_sum as isize),
+                fmt_thousands(current_sum as isize),
+                change_str
+            );
+        } else {
+            md!(md_w, "\n_All significant changes are listed above._\n");
+        }
+
+        md!(md_w, "\n");
+    }
 
     // ── Per-function LLVM IR line changes ─────────────────────────────────────
 
@@ -458,6 +680,29 @@ pub(crate) fn generate_reports(
         line_diff: isize,
     }
 
+    impl ComparativeFn<'_> {
+        /// Whether the function was instantiated a different number of times
+        fn copies_changed(&self) -> bool {
+            match (self.old, self.new) {
+                (Some(old), Some(new)) => old.copies != new.copies,
+                _ => false,
+            }
+        }
+    }
+
+    /// Number of copies and lines per copy, e.g. `3 × 120`
+    fn fmt_copies(f: Option<&AggregateLlvmFunction>) -> String {
+        f.map(|f| {
+            let copies = f.copies.value();
+            format!(
+                "{} × {}",
+                fmt_thousands(copies as isize),
+                fmt_thousands((f.total_llvm_lines.value() / copies.max(1)) as isize)
+            )
+        })
+        .unwrap_or_else(|| "—".to_string())
+    }
+
     let mut comparative_fns: Vec<ComparativeFn> = fn_names
         .iter()
         .map(|&name| {
@@ -474,11 +719,11 @@ pub(crate) fn generate_reports(
                 line_diff,
             }
         })
-        .filter(|f| f.line_diff != 0)
+        .filter(|f| f.line_diff != 0 || f.copies_changed())
         .collect();
 
     // Sort by absolute line difference (largest first)
-    comparative_fns.sort_by_key(|f| cmp::Reverse(f.line_diff.abs() as u64));
+    comparative_fns.sort_by_key(|f| cmp::Reverse(f.line_diff.unsigned_abs()));
 
     // Split into detailed (top 20) and excluded
     let detailed_fns: Vec<&ComparativeFn> = comparative_fns.iter().take(20).collect();
@@ -486,8 +731,8 @@ pub(crate) fn generate_reports(
 
     if !detailed_fns.is_empty() {
         // Markdown table with explicit old/new/diff columns
-        md!("| Function | Baseline Lines | Current Lines | Change |\n");
-        md!("|----------|---------------|--------------|--------|\n");
+        md!(md_w, "| Function | Baseline Lines | Current Lines | Change | Baseline Copies × Lines | Current Copies × Lines |\n");
+        md!(md_w, "|----------|---------------|--------------|--------|-------------------------|------------------------|\n");
 
         for f in &detailed_fns {
             let name = f
@@ -530,31 +775,45 @@ pub(crate) fn generate_reports(
                 .map(|ln| fmt_thousands(ln as isize))
                 .unwrap_or_else(|| "—".to_string());
 
-            let diff = new_ln as isize - old_ln as isize;
             let change_str = if baseline_lines.is_some() && current_lines.is_some() {
-                if diff > 0 {
-                    format!("📈 +{}", fmt_thousands(diff))
-                } else if diff < 0 {
-                    format!("📉 -{}", fmt_thousands((-diff) as isize))
-                } else {
-                    "➖ no change".to_owned()
-                }
+                change_cell(
+                    || format!("function `{}` LLVM lines", name),
+                    old_ln as u64,
+                    new_ln as u64,
+                    |n| fmt_thousands(n as isize),
+                    thresholds,
+                    &mut findings,
+                )
             } else if baseline_lines.is_none() && current_lines.is_some() {
-                "🆕 NEW".to_owned()
+                new_cell(
+                    || format!("function `{}` LLVM lines", name),
+                    new_ln as u64,
+                    baseline.num_llvm_lines() as u64,
+                    |n| fmt_thousands(n as isize),
+                    thresholds,
+                    &mut findings,
+                )
             } else if baseline_lines.is_some() && current_lines.is_none() {
                 "🗑️ REMOVED".to_owned()
             } else {
                 "—".to_owned()
             };
 
+            // Flag functions that were instantiated a different number of times
+            let copies_flag = if f.copies_changed() { " 🔁" } else { "" };
+
             // Final row
             md!(
-                "| `{}`{} | {} | {} | {} |\n",
+                md_w,
+                "| `{}`{} | {} | {} | {} | {} | {}{} |\n",
                 name,
                 crates_cell,
                 baseline_fmt,
                 current_fmt,
-                change_str
+                change_str,
+                fmt_copies(f.old),
+                fmt_copies(f.new),
+                copies_flag
             );
         }
 
@@ -569,16 +828,17 @@ pub(crate) fn generate_reports(
                 .map(|f| f.new.map(|v| v.total_llvm_lines.value()).unwrap_or(0))
                 .sum();
 
-            let diff = current_sum as isize - baseline_sum as isize;
-            let change_str = if diff > 0 {
-                format!("📈 +{}", fmt_thousands(diff))
-            } else if diff < 0 {
-                format!("📉 -{}", fmt_thousands((-diff) as isize))
-            } else {
-                "➖ no change".to_string()
-            };
+            let change_str = change_cell(
+                || format!("{} additional functions LLVM lines", excluded_fns.len()),
+                baseline_sum as u64,
+                current_sum as u64,
+                |n| fmt_thousands(n as isize),
+                thresholds,
+                &mut findings,
+            );
 
             md!(
+                md_w,
                 "\n*{} additional functions account for **{}** → **{}** ({})*\n",
                 excluded_fns.len(),
                 fmt_thousands(baseline_sum as isize),
@@ -586,13 +846,120 @@ pub(crate) fn generate_reports(
                 change_str
             );
         } else {
-            md!("\n_All significant changes are listed above._\n");
+            md!(md_w, "\n_All significant changes are listed above._\n");
         }
 
-        md!("\n");
+        let recopied = comparative_fns
+            .iter()
+            .filter(|f| f.copies_changed())
+            .count();
+        if recopied > 0 {
+            md!(
+                md_w,
+                "\n_🔁 marks a changed number of copies ({} functions in total)._\n",
+                recopied
+            );
+        }
+
+        md!(md_w, "\n");
+    }
+
+    // Size-only builds may run at once, so their durations say nothing
+    if !provenance.size_only {
+        generate_wall_duration_report(baseline, current, tx_w, md_w);
+    }
+
+    // Header, then the summary of findings, then the body
+
+    const TOP_N_FINDINGS: usize = 15;
+
+    findings.sort_by_key(|f| cmp::Reverse(f.severity));
+    let critical = findings
+        .iter()
+        .filter(|f| f.severity == Severity::Critical)
+        .count();
+    let warnings = findings.len() - critical;
+
+    tx!(tx_out, "{}", "limpid report\n".bright_blue());
+    md!(md_out, "# 📦 limpid report\n\n");
+
+    tx!(
+        tx_out,
+        "Scenario: {} ({})\n",
+        scenario.bin.blue(),
+        scenario.description
+    );
+    md!(
+        md_out,
+        "Scenario: `{}` ({})  \n",
+        scenario.bin,
+        scenario.description
+    );
+    if let Some((old, new)) = &provenance.toolchains {
+        tx!(
+            tx_out,
+            "Toolchains: {} → {} (same facet checkout)\n",
+            old.yellow(),
+            new.yellow()
+        );
+        md!(
+            md_out,
+            "Toolchains: `{}` → `{}` (same facet checkout)  \n",
+            old,
+            new
+        );
+    }
+
+    if findings.is_empty() {
+        let message = format!("✅ No growth past the warn threshold ({})", thresholds);
+        tx!(tx_out, "{}\n", message.green());
+        md!(md_out, "{}  \n", message);
+    } else {
+        let counts = format!(
+            "🚨 {} critical, ⚠️ {} warnings ({})",
+            critical, warnings, thresholds
+        );
+        tx!(tx_out, "{}\n", counts.yellow());
+        md!(md_out, "\n### 🚦 Summary\n\n{}\n\n", counts);
+        for f in findings.iter().take(TOP_N_FINDINGS) {
+            tx!(
+                tx_out,
+                "  {} {}: {}\n",
+                f.severity.marker(),
+                f.what,
+                f.change
+            );
+            md!(
+                md_out,
+                "- {} {}: {}\n",
+                f.severity.marker(),
+                f.what,
+                f.change
+            );
+        }
+        if findings.len() > TOP_N_FINDINGS {
+            let more = findings.len() - TOP_N_FINDINGS;
+            tx!(tx_out, "  ...and {} more\n", more);
+            md!(md_out, "- ...and {} more\n", more);
+        }
+        md!(md_out, "\n");
     }
 
-    // Compare total build time (wall_duration)
+    generate_provenance_report(provenance, tx_out, md_out);
+
+    tx_out.push_str(&tx_body);
+    md_out.push_str(&md_body);
+
+    Ok(())
+}
+
+/// Write the build time of both sides
+fn generate_wall_duration_report(
+    baseline: &BuildContext,
+    current: &BuildContext,
+    tx_w: &mut String,
+    md_w: &mut String,
+) {
     let baseline_secs = baseline.wall_duration.as_secs_f64();
     let current_secs = current.wall_duration.as_secs_f64();
 
@@ -611,25 +978,1278 @@ pub(crate) fn generate_reports(
         }
     }
 
-    tx!("Wall duration: {}", fmt_duration(current_secs).magenta());
-    md!("Wall duration: {}", fmt_duration(current_secs));
+    tx!(
+        tx_w,
+        "Wall duration: {}",
+        fmt_duration(current_secs).magenta()
+    );
+    md!(md_w, "Wall duration: {}", fmt_duration(current_secs));
+    // Build times are too noisy to be given a severity
     let diff = current_secs - baseline_secs;
+    let percent = fmt_percent(
+        baseline.wall_duration.as_millis() as u64,
+        current.wall_duration.as_millis() as u64,
+    );
     if diff > 0.01 {
-        tx!("{}", format!(" (📈 +{:.2} s)", diff).green());
-        md!(" (📈 +{:.2} s)", diff);
+        tx!(
+            tx_w,
+            "{}",
+            format!(" (📈 +{:.2} s{})", diff, percent).green()
+        );
+        md!(md_w, " (📈 +{:.2} s{})", diff, percent);
     } else if diff < -0.01 {
-        tx!("{}", format!(" (📉 {:.2} s)", diff).red());
-        md!(" (📉 {:.2} s)", diff);
+        tx!(tx_w, "{}", format!(" (📉 {:.2} s{})", diff, percent).red());
+        md!(md_w, " (📉 {:.2} s{})", diff, percent);
     } else {
-        tx!("{}", " (➖ no change)".dimmed());
-        md!(" (➖ no change)");
+        tx!(tx_w, "{}", " (➖ no change)".dimmed());
+        md!(md_w, " (➖ no change)");
+    }
+    tx!(tx_w, "\n");
+    md!(md_w, "  \n");
+}
+
+/// Write what the report compared: commits, toolchain, build settings and host
+fn generate_provenance_report(provenance: &Provenance, tx_w: &mut String, md_w: &mut String) {
+    let build_env = provenance
+        .build_env
+        .iter()
+        .map(|(var, value)| format!("{}={}", var, value))
+        .join(" ");
+
+    let rows = [
+        (
+            "facet baseline",
+            match &provenance.facet_baseline_ref {
+                Some(baseline_ref) => format!(
+                    "{} (from {})",
+                    provenance.facet_baseline.describe(),
+                    baseline_ref
+                ),
+                None => provenance.facet_baseline.describe(),
+            },
+        ),
+        ("facet current", provenance.facet_current.describe()),
+        ("limpid / kitchensink", provenance.limpid.describe()),
+        ("rustc", provenance.rustc.clone()),
+        ("cargo", provenance.cargo.clone()),
+        ("target", provenance.target.clone()),
+        ("profile", provenance.profile.clone()),
+        (
+            "measured",
+            if provenance.size_only {
+                "sizes only".to_owned()
+            } else {
+                "sizes and build times".to_owned()
+            },
+        ),
+        ("RUSTFLAGS", provenance.rustflags.clone()),
+        ("build env", build_env),
+        (
+            "host",
+            format!("{} ({} cores)", provenance.cpu, provenance.cores),
+        ),
+        ("generated", provenance.timestamp.clone()),
+    ];
+
+    md!(md_w, "\n<details><summary>🧾 Provenance</summary>\n\n");
+    md!(md_w, "| | |\n");
+    md!(md_w, "|-|-|\n");
+    for (label, value) in &rows {
+        tx!(tx_w, "{} {}\n", format!("{}:", label).dimmed(), value);
+        md!(md_w, "| {} | `{}` |\n", label, value.replace('|', "\\|"));
+    }
+    md!(md_w, "\n</details>\n\n");
+}
+
+/// Generate the joined function view: LLVM lines next to the bytes of the
+/// symbols each function was emitted as
+pub(crate) fn generate_function_cost_report(
+    baseline: &BuildContext,
+    current: &BuildContext,
+    tx_w: &mut String,
+    md_w: &mut String,
+) -> anyhow::Result<()> {
+    const TOP_N_FUNCTIONS: usize = 20;
+
+    /// IR lines of a function and bytes of the symbols of the same name
+    #[derive(Clone, Copy, Default)]
+    struct FunctionCost {
+        lines: usize,
+        bytes: u64,
+    }
+
+    impl FunctionCost {
+        /// Bytes of machine code per line of IR
+        fn ratio(&self) -> Option<f64> {
+            (self.lines > 0).then(|| self.bytes as f64 / self.lines as f64)
+        }
+    }
+
+    // Symbols and LLVM functions are demangled the same way, with the hash
+    // stripped, so a function's symbols are the ones with its name
+    fn function_costs(context: &BuildContext) -> BTreeMap<String, FunctionCost> {
+        let symbols = context.all_symbols();
+        context
+            .all_llvm_functions()
+            .into_values()
+            .filter(|f| !f.name.as_str().starts_with("autocfg_"))
+            .map(|f| {
+                let bytes = symbols
+                    .get(f.name.as_str())
+                    .map(|s| s.total_size.value())
+                    .unwrap_or(0);
+                let cost = FunctionCost {
+                    lines: f.total_llvm_lines.value(),
+                    bytes,
+                };
+                (f.name.to_string(), cost)
+            })
+            .collect()
+    }
+
+    fn fmt_pair<T>(old: Option<T>, new: Option<T>, fmt: impl Fn(T) -> String) -> String {
+        format!(
+            "{} → {}",
+            old.map(&fmt).unwrap_or_else(|| "—".to_owned()),
+            new.map(&fmt).unwrap_or_else(|| "—".to_owned())
+        )
+    }
+
+    let baseline_costs = function_costs(baseline);
+    let current_costs = function_costs(current);
+
+    // The functions that cost the most IR on either side
+    let top: Vec<&str> = baseline_costs
+        .keys()
+        .chain(current_costs.keys())
+        .map(|name| name.as_str())
+        .collect::<BTreeSet<&str>>()
+        .into_iter()
+        .sorted_by_key(|name| {
+            let old = baseline_costs.get(*name).map(|c| c.lines).unwrap_or(0);
+            let new = current_costs.get(*name).map(|c| c.lines).unwrap_or(0);
+            cmp::Reverse(old.max(new))
+        })
+        .take(TOP_N_FUNCTIONS)
+        .collect();
+
+    tx!(
+        tx_w,
+        "{}",
+        "Function costs (LLVM lines vs. bytes)\n".bright_blue()
+    );
+    md!(md_w, "\n## ⚖️ Function costs\n\n");
+    md!(md_w, "The functions with the most LLVM IR, next to the bytes of the symbols they were emitted as. A high bytes/line ratio is expensive to ship; a low one mostly costs compile time.\n\n");
+    md!(md_w, "| Function | LLVM Lines | Bytes | Bytes/Line |\n");
+    md!(md_w, "|----------|------------|-------|------------|\n");
+
+    for name in top {
+        let old = baseline_costs.get(name).copied();
+        let new = current_costs.get(name).copied();
+        let lines = fmt_pair(old, new, |c| fmt_thousands(c.lines as isize));
+        let bytes = fmt_pair(old, new, |c| format_bytes(c.bytes));
+        let ratio = fmt_pair(
+            old.and_then(|c| c.ratio()),
+            new.and_then(|c| c.ratio()),
+            |r| format!("{:.1}", r),
+        );
+        tx!(
+            tx_w,
+            "  {}: {} lines, {}, {} bytes/line\n",
+            name.blue(),
+            lines,
+            bytes,
+            ratio
+        );
+        md!(md_w, "| `{}` | {} | {} | {} |\n", name, lines, bytes, ratio);
+    }
+    md!(md_w, "\n");
+
+    // IR that never shows up as a symbol was inlined into its callers or discarded
+    let unemitted = |costs: &BTreeMap<String, FunctionCost>| {
+        let missing = costs.values().filter(|c| c.bytes == 0);
+        (
+            missing.clone().count(),
+            missing.map(|c| c.lines).sum::<usize>(),
+        )
+    };
+    let (old_count, old_lines) = unemitted(&baseline_costs);
+    let (new_count, new_lines) = unemitted(&current_costs);
+    tx!(
+        tx_w,
+        "  Functions without a symbol (inlined or discarded): {} → {}, {} → {} LLVM lines\n",
+        fmt_thousands(old_count as isize),
+        fmt_thousands(new_count as isize),
+        fmt_thousands(old_lines as isize),
+        fmt_thousands(new_lines as isize)
+    );
+    md!(
+        md_w,
+        "Functions without a symbol (inlined or discarded): {} → {}, accounting for {} → {} LLVM lines  \n",
+        fmt_thousands(old_count as isize),
+        fmt_thousands(new_count as isize),
+        fmt_thousands(old_lines as isize),
+        fmt_thousands(new_lines as isize)
+    );
+
+    Ok(())
+}
+
+/// Generate the category section: bytes and LLVM lines per facet-specific category
+pub(crate) fn generate_category_report(
+    rules: &CategoryRules,
+    baseline: &BuildContext,
+    current: &BuildContext,
+    tx_w: &mut String,
+    md_w: &mut String,
+) -> anyhow::Result<()> {
+    fn change_str(old: u64, new: u64, fmt: impl Fn(u64) -> String) -> String {
+        match new.cmp(&old) {
+            cmp::Ordering::Greater => format!("📈 +{}", fmt(new - old)),
+            cmp::Ordering::Less => format!("📉 -{}", fmt(old - new)),
+            cmp::Ordering::Equal => "➖ no change".to_owned(),
+        }
     }
-    tx!("\n");
-    md!("  \n");
+
+    let lines = |n: u64| fmt_thousands(n as isize);
+    let baseline_sizes = category_sizes(rules, baseline);
+    let current_sizes = category_sizes(rules, current);
+
+    tx!(tx_w, "{}", "Categories\n".bright_blue());
+    md!(md_w, "\n## 🗂️ Categories\n\n");
+    md!(
+        md_w,
+        "Symbols and LLVM functions sorted by the rules of `categories.txt`.\n\n"
+    );
+    md!(md_w, "| Category | Baseline Size | Current Size | Change | Baseline LLVM Lines | Current LLVM Lines | Change |\n");
+    md!(md_w, "|----------|---------------|--------------|--------|---------------------|--------------------|--------|\n");
+
+    for (old, new) in baseline_sizes.iter().zip(&current_sizes) {
+        if old.symbols + new.symbols + old.llvm_functions + new.llvm_functions == 0 {
+            continue;
+        }
+        let bytes_change = change_str(old.bytes, new.bytes, format_bytes);
+        let (old_lines, new_lines) = (old.llvm_lines as u64, new.llvm_lines as u64);
+        let lines_change = change_str(old_lines, new_lines, lines);
+
+        tx!(
+            tx_w,
+            "  {}: {} → {} ({}), {} → {} LLVM lines ({})\n",
+            new.name.blue(),
+            format_bytes(old.bytes),
+            format_bytes(new.bytes),
+            bytes_change,
+            lines(old_lines),
+            lines(new_lines),
+            lines_change
+        );
+        md!(
+            md_w,
+            "| {} | {} | {} | {} | {} | {} | {} |\n",
+            new.name,
+            format_bytes(old.bytes),
+            format_bytes(new.bytes),
+            bytes_change,
+            lines(old_lines),
+            lines(new_lines),
+            lines_change
+        );
+    }
+    md!(md_w, "\n");
 
     Ok(())
 }
 
+/// Generate the dependency graph section: crates added, removed, changed and duplicated
+pub(crate) fn generate_dependency_report(
+    baseline: &DependencyGraph,
+    current: &DependencyGraph,
+    tx_w: &mut String,
+    md_w: &mut String,
+) -> anyhow::Result<()> {
+    fn versions(v: &BTreeSet<String>) -> String {
+        v.iter().join(", ")
+    }
+
+    let diff = DependencyDiff::between(baseline, current);
+
+    tx!(tx_w, "{}", "Dependencies\n".bright_blue());
+    md!(md_w, "\n## 📦 Dependencies\n\n");
+
+    if diff.is_empty() {
+        tx!(
+            tx_w,
+            "  No changes to the resolved graph ({} packages)\n",
+            current.versions.len()
+        );
+        md!(
+            md_w,
+            "No changes to the resolved dependency graph ({} packages).\n\n",
+            current.versions.len()
+        );
+    } else {
+        md!(md_w, "| Crate | Baseline | Current | Pulled in by |\n");
+        md!(md_w, "|-------|----------|---------|--------------|\n");
+
+        for (name, new) in &diff.added {
+            let path = new
+                .iter()
+                .find_map(|version| current.path_to(name, version))
+                .unwrap_or_default();
+            tx!(
+                tx_w,
+                "  🆕 {} {} ({})\n",
+                name.green(),
+                versions(new),
+                path.dimmed()
+            );
+            md!(
+                md_w,
+                "| `{}` 🆕 NEW | — | {} | {} |\n",
+                name,
+                versions(new),
+                path
+            );
+        }
+        for (name, old) in &diff.removed {
+            let path = old
+                .iter()
+                .find_map(|version| baseline.path_to(name, version))
+                .unwrap_or_default();
+            tx!(
+                tx_w,
+                "  🗑️  {} {} ({})\n",
+                name.red(),
+                versions(old),
+                path.dimmed()
+            );
+            md!(
+                md_w,
+                "| `{}` 🗑️ REMOVED | {} | — | {} |\n",
+                name,
+                versions(old),
+                path
+            );
+        }
+        for (name, old, new) in &diff.changed {
+            // Prefer the path to a version the change brought in over one
+            // that was already there
+            let path = new
+                .difference(old)
+                .chain(new)
+                .find_map(|version| current.path_to(name, version))
+                .unwrap_or_default();
+            tx!(
+                tx_w,
+                "  🔄 {} {} → {}\n",
+                name.yellow(),
+                versions(old),
+                versions(new)
+            );
+            md!(
+                md_w,
+                "| `{}` | {} | {} | {} |\n",
+                name,
+                versions(old),
+                versions(new),
+                path
+            );
+        }
+        md!(md_w, "\n");
+    }
+
+    if !diff.duplicated.is_empty() {
+        md!(
+            md_w,
+            "Crates with several versions in the current graph:\n\n"
+        );
+        md!(md_w, "| Crate | Versions | In baseline |\n");
+        md!(md_w, "|-------|----------|-------------|\n");
+        for (name, all, before) in &diff.duplicated {
+            let marker = if *before { "yes" } else { "🆕 NEW" };
+            tx!(
+                tx_w,
+                "  ⚠️  {} in several versions: {} {}\n",
+                name,
+                versions(all),
+                marker
+            );
+            md!(md_w, "| `{}` | {} | {} |\n", name, versions(all), marker);
+        }
+        md!(md_w, "\n");
+    }
+
+    Ok(())
+}
+
+/// Generate the rustc memory usage section of the text and markdown reports
+pub(crate) fn generate_memory_report(
+    baseline: &RustcMemory,
+    current: &RustcMemory,
+    tx_w: &mut String,
+    md_w: &mut String,
+) -> anyhow::Result<()> {
+    fn change_str(old: Option<u64>, new: Option<u64>) -> String {
+        match (old, new) {
+            (Some(old), Some(new)) => {
+                let diff = new as i64 - old as i64;
+                if diff > 0 {
+                    format!("📈 +{}", format_bytes(diff as u64))
+                } else if diff < 0 {
+                    format!("📉 -{}", format_bytes((-diff) as u64))
+                } else {
+                    "➖ no change".to_owned()
+                }
+            }
+            (None, Some(_)) => "🆕 NEW".to_owned(),
+            (Some(_), None) => "🗑️ REMOVED".to_owned(),
+            (None, None) => "—".to_owned(),
+        }
+    }
+
+    let (baseline_peak, current_peak) = (baseline.peak(), current.peak());
+    let peak_change = change_str(Some(baseline_peak), Some(current_peak));
+
+    tx!(
+        tx_w,
+        "Peak rustc memory: {} ({})\n",
+        format_bytes(current_peak).magenta(),
+        peak_change
+    );
+    md!(md_w, "\n## 🧠 rustc memory usage\n\n");
+    md!(
+        md_w,
+        "Peak rustc memory: {} ({})  \n\n",
+        format_bytes(current_peak),
+        peak_change
+    );
+
+    // Show the crates that are the most expensive to compile on either side
+    const TOP_N_CRATES: usize = 15;
+
+    let crate_names: BTreeSet<&str> = baseline
+        .per_crate
+        .keys()
+        .chain(current.per_crate.keys())
+        .map(|name| name.as_str())
+        .collect();
+    let top_crates: Vec<&str> = crate_names
+        .into_iter()
+        .sorted_by_key(|name| {
+            let old = baseline.per_crate.get(*name).copied().unwrap_or(0);
+            let new = current.per_crate.get(*name).copied().unwrap_or(0);
+            cmp::Reverse(old.max(new))
+        })
+        .take(TOP_N_CRATES)
+        .collect();
+
+    if !top_crates.is_empty() {
+        md!(
+            md_w,
+            "| Crate | Baseline Peak RSS | Current Peak RSS | Change |\n"
+        );
+        md!(
+            md_w,
+            "|-------|-------------------|------------------|--------|\n"
+        );
+
+        for name in top_crates {
+            let old = baseline.per_crate.get(name).copied();
+            let new = current.per_crate.get(name).copied();
+            md!(
+                md_w,
+                "| `{}` | {} | {} | {} |\n",
+                name,
+                old.map(format_bytes).unwrap_or_else(|| "—".to_string()),
+                new.map(format_bytes).unwrap_or_else(|| "—".to_string()),
+                change_str(old, new)
+            );
+        }
+        md!(md_w, "\n");
+    }
+
+    Ok(())
+}
+
+/// Generate the heap allocation section of the text and markdown reports
+pub(crate) fn generate_alloc_report(
+    baseline: &AllocResult,
+    current: &AllocResult,
+    serde: &AllocResult,
+    tx_w: &mut String,
+    md_w: &mut String,
+) -> anyhow::Result<()> {
+    type Metric = (&'static str, fn(&AllocStats) -> u64, fn(u64) -> String);
+    let metrics: [Metric; 3] = [
+        (
+            "allocations",
+            |s| s.allocations,
+            |n| fmt_thousands(n as isize),
+        ),
+        ("bytes allocated", |s| s.bytes, format_bytes),
+        ("peak live heap", |s| s.peak, format_bytes),
+    ];
+
+    tx!(tx_w, "{}", "Heap allocations\n".bright_blue());
+    md!(md_w, "\n## 🧮 Heap allocations\n\n");
+    md!(
+        md_w,
+        "| Operation | Metric | Baseline | Current | Change | serde (reference) |\n"
+    );
+    md!(
+        md_w,
+        "|-----------|--------|----------|---------|--------|-------------------|\n"
+    );
+
+    let operations: [(&str, &AllocStats, &AllocStats, &AllocStats); 2] = [
+        (
+            "serialize",
+            &baseline.serialize,
+            &current.serialize,
+            &serde.serialize,
+        ),
+        (
+            "deserialize",
+            &baseline.deserialize,
+            &current.deserialize,
+            &serde.deserialize,
+        ),
+    ];
+
+    for (operation, old, new, reference) in operations {
+        for (metric, get, fmt) in &metrics {
+            let (old, new, reference) = (get(old), get(new), get(reference));
+            let diff = new as i64 - old as i64;
+            let change_str = if diff > 0 {
+                format!("📈 +{}", fmt(diff as u64))
+            } else if diff < 0 {
+                format!("📉 -{}", fmt((-diff) as u64))
+            } else {
+                "➖ no change".to_owned()
+            };
+
+            tx!(
+                tx_w,
+                "  {} {}: {} → {} ({})\n",
+                operation,
+                metric,
+                fmt(old),
+                fmt(new).magenta(),
+                change_str
+            );
+            md!(
+                md_w,
+                "| {} | {} | {} | {} | {} | {} |\n",
+                operation,
+                metric,
+                fmt(old),
+                fmt(new),
+                change_str,
+                fmt(reference)
+            );
+        }
+    }
+    md!(md_w, "\n");
+
+    Ok(())
+}
+
+/// Generate the runtime benchmark section of the text and markdown reports
+pub(crate) fn generate_bench_report(
+    baseline: &BenchResult,
+    current: &BenchResult,
+    tx_w: &mut String,
+    md_w: &mut String,
+) -> anyhow::Result<()> {
+    tx!(tx_w, "{}", "Runtime benchmark\n".bright_blue());
+    md!(md_w, "\n## ⏱️ Runtime\n\n");
+    md!(
+        md_w,
+        "Median of the timed iterations, on a {} JSON document.\n\n",
+        format_bytes(current.json_bytes)
+    );
+    md!(md_w, "| Operation | Baseline | Current | Change |\n");
+    md!(md_w, "|-----------|----------|---------|--------|\n");
+
+    let rows: [(&str, &BenchTiming, &BenchTiming); 2] = [
+        ("serialize", &baseline.serialize, &current.serialize),
+        ("deserialize", &baseline.deserialize, &current.deserialize),
+    ];
+
+    for (name, old, new) in rows {
+        let old_fmt = format!(
+            "{} ({}/s)",
+            fmt_nanos(old.median),
+            format_bytes(old.throughput(baseline.json_bytes) as u64)
+        );
+        let new_fmt = format!(
+            "{} ({}/s)",
+            fmt_nanos(new.median),
+            format_bytes(new.throughput(current.json_bytes) as u64)
+        );
+
+        // Compare throughput rather than raw timings, in case the document size changed
+        let old_tp = old.throughput(baseline.json_bytes);
+        let new_tp = new.throughput(current.json_bytes);
+        let pct = (new_tp - old_tp) / old_tp * 100.0;
+        let change_str = if pct > 1.0 {
+            format!("🚀 +{:.1}% throughput", pct)
+        } else if pct < -1.0 {
+            format!("🐢 {:.1}% throughput", pct)
+        } else {
+            "➖ no change".to_owned()
+        };
+
+        tx!(
+            tx_w,
+            "  {}: {} → {} ({})\n",
+            name,
+            old_fmt,
+            new_fmt.magenta(),
+            change_str
+        );
+        md!(
+            md_w,
+            "| {} | {}<br><sub>{} – {}</sub> | {}<br><sub>{} – {}</sub> | {} |\n",
+            name,
+            old_fmt,
+            fmt_nanos(old.min),
+            fmt_nanos(old.max),
+            new_fmt,
+            fmt_nanos(new.min),
+            fmt_nanos(new.max),
+            change_str
+        );
+    }
+    md!(md_w, "\n");
+
+    Ok(())
+}
+
+/// Generate the facet/serde correctness section of the text and markdown reports
+pub(crate) fn generate_correctness_report(
+    baseline: &CorrectnessResult,
+    current: &CorrectnessResult,
+    tx_w: &mut String,
+    md_w: &mut String,
+) -> anyhow::Result<()> {
+    fn outcome_str(outcome: Option<&CheckOutcome>) -> String {
+        match outcome {
+            Some(CheckOutcome::Ok) => "✅ ok".to_owned(),
+            Some(CheckOutcome::Mismatch { count, .. }) => format!("❌ {} differences", count),
+            Some(CheckOutcome::Error(_)) => "💥 error".to_owned(),
+            None => "—".to_owned(),
+        }
+    }
+
+    /// Escape a value so it can be put in a markdown table cell
+    fn cell(s: &str) -> String {
+        s.replace('|', "\\|")
+    }
+
+    tx!(tx_w, "{}", "Correctness (facet vs serde)\n".bright_blue());
+    md!(md_w, "\n## 🔍 Correctness\n\n");
+    md!(md_w, "| Check | Baseline | Current |\n");
+    md!(md_w, "|-------|----------|---------|\n");
+
+    for check in &current.checks {
+        let (description, _, _) = describe_check(&check.name);
+        let old = outcome_str(baseline.get(&check.name));
+        let new = outcome_str(Some(&check.outcome));
+
+        let new_colored = match check.outcome {
+            CheckOutcome::Ok => new.green().to_string(),
+            _ => new.red().to_string(),
+        };
+        tx!(tx_w, "  {}: {} → {}\n", check.name, old, new_colored);
+        md!(
+            md_w,
+            "| `{}`<br>{} | {} | {} |\n",
+            check.name,
+            description,
+            old,
+            new
+        );
+    }
+    md!(md_w, "\n");
+
+    // Details are only shown for the current build: that's what the PR changes
+    for check in &current.checks {
+        let (_, left_label, right_label) = describe_check(&check.name);
+        match &check.outcome {
+            CheckOutcome::Ok => {}
+            CheckOutcome::Mismatch { count, diffs } => {
+                md!(
+                    md_w,
+                    "<details><summary><code>{}</code>: {} differences</summary>\n\n",
+                    check.name,
+                    count
+                );
+                md!(md_w, "| Path | {} | {} |\n", left_label, right_label);
+                md!(md_w, "|------|------|------|\n");
+                for diff in diffs {
+                    md!(
+                        md_w,
+                        "| `{}` | `{}` | `{}` |\n",
+                        cell(&diff.pointer),
+                        cell(&diff.left),
+                        cell(&diff.right)
+                    );
+                }
+                if *count > diffs.len() {
+                    md!(
+                        md_w,
+                        "\n_{} more differences not shown._\n",
+                        count - diffs.len()
+                    );
+                }
+                md!(md_w, "\n</details>\n\n");
+            }
+            CheckOutcome::Error(message) => {
+                tx!(tx_w, "    {}\n", message.dimmed());
+                md!(
+                    md_w,
+                    "<details><summary><code>{}</code>: error</summary>\n\n```\n{}\n```\n\n</details>\n\n",
+                    check.name,
+                    message
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Report which types of `ks_types::unused` survived into the derive-only binary
+pub(crate) fn generate_derive_only_report(
+    baseline: &DeriveOnlyResult,
+    current: &DeriveOnlyResult,
+    tx_w: &mut String,
+    md_w: &mut String,
+) -> anyhow::Result<()> {
+    fn status_str(result: &DeriveOnlyResult) -> String {
+        if result.survivors.is_empty() {
+            format!("✅ none of {} survive", result.types_checked)
+        } else {
+            format!(
+                "❌ {} of {} survive",
+                result.survivors.len(),
+                result.types_checked
+            )
+        }
+    }
+
+    let old = status_str(baseline);
+    let new = status_str(current);
+    let new_colored = if current.survivors.is_empty() {
+        new.green().to_string()
+    } else {
+        new.red().to_string()
+    };
+
+    tx!(
+        tx_w,
+        "{}",
+        "Unused derived types (ks_types::unused)\n".bright_blue()
+    );
+    tx!(tx_w, "  {} → {}\n", old, new_colored);
+    md!(md_w, "\n## 🧹 Unused derived types\n\n");
+    md!(
+        md_w,
+        "Types of `ks_types::unused` found in the derive-only binary: {} → {}\n\n",
+        old,
+        new
+    );
+
+    if current.survivors.is_empty() {
+        return Ok(());
+    }
+
+    md!(md_w, "| Type | Symbols | Shape docs | In baseline |\n");
+    md!(md_w, "|------|--------:|-----------:|-------------|\n");
+    for survivor in &current.survivors {
+        let in_baseline = baseline.survivors.iter().any(|s| s.name == survivor.name);
+        let marker = if in_baseline { "yes" } else { "🆕 NEW" };
+        tx!(
+            tx_w,
+            "  {} ({} symbols, {} shape docs) {}\n",
+            survivor.name.red(),
+            survivor.symbols,
+            survivor.docs,
+            marker
+        );
+        md!(
+            md_w,
+            "| `{}` | {} | {} | {} |\n",
+            survivor.name,
+            survivor.symbols,
+            survivor.docs,
+            marker
+        );
+    }
+    md!(md_w, "\n");
+
+    Ok(())
+}
+
+/// Report how build cost grows with the number of synthetic types
+pub(crate) fn generate_scaling_report(
+    baseline: &[ScalingPoint],
+    current: &[ScalingPoint],
+    tx_w: &mut String,
+    md_w: &mut String,
+) -> anyhow::Result<()> {
+    /// Cost of one more type: slope between the smallest and largest crate
+    fn per_type(points: &[ScalingPoint], metric: impl Fn(&ScalingPoint) -> f64) -> Option<f64> {
+        let (first, last) = (points.first()?, points.last()?);
+        if last.types == first.types {
+            return None;
+        }
+        Some((metric(last) - metric(first)) / (last.types - first.types) as f64)
+    }
+
+    fn fmt_per_type(v: Option<f64>, fmt: impl Fn(f64) -> String) -> String {
+        v.map(fmt).unwrap_or_else(|| "—".to_owned())
+    }
+
+    tx!(tx_w, "{}", "Scaling (synthetic types)\n".bright_blue());
+    md!(md_w, "\n## 📐 Scaling\n\n");
+    md!(md_w, "Build cost of a generated crate with N types deriving `Facet`, round-tripped through facet-json.\n\n");
+    md!(md_w, "| Types | Build time | .text size | LLVM lines |\n");
+    md!(md_w, "|------:|------------|------------|------------|\n");
+
+    for new in current {
+        let old = baseline.iter().find(|p| p.types == new.types);
+        let time = format!(
+            "{} → {}",
+            old.map(|p| format!("{:.2}s", p.wall_duration.as_secs_f64()))
+                .unwrap_or_else(|| "—".to_owned()),
+            format_args!("{:.2}s", new.wall_duration.as_secs_f64())
+        );
+        let text = format!(
+            "{} → {}",
+            old.map(|p| format_bytes(p.text_size))
+                .unwrap_or_else(|| "—".to_owned()),
+            format_bytes(new.text_size)
+        );
+        let lines = format!(
+            "{} → {}",
+            old.map(|p| fmt_thousands(p.llvm_lines as isize))
+                .unwrap_or_else(|| "—".to_owned()),
+            fmt_thousands(new.llvm_lines as isize)
+        );
+        tx!(
+            tx_w,
+            "  {:>5} types: {}, {}, {} LLVM lines\n",
+            new.types,
+            time,
+            text,
+            lines
+        );
+        md!(
+            md_w,
+            "| {} | {} | {} | {} |\n",
+            new.types,
+            time,
+            text,
+            lines
+        );
+    }
+    md!(md_w, "\n");
+
+    let secs = |p: &ScalingPoint| p.wall_duration.as_secs_f64();
+    let text = |p: &ScalingPoint| p.text_size as f64;
+    let lines = |p: &ScalingPoint| p.llvm_lines as f64;
+    let fmt_secs = |v: f64| format!("{:.1}ms", v * 1000.0);
+    let fmt_size = |v: f64| format_bytes(v.max(0.0) as u64);
+    let fmt_lines = |v: f64| fmt_thousands(v.round() as isize);
+
+    md!(md_w, "Cost per additional type (baseline → current):  \n");
+    for (label, old, new) in [
+        (
+            "build time",
+            fmt_per_type(per_type(baseline, secs), fmt_secs),
+            fmt_per_type(per_type(current, secs), fmt_secs),
+        ),
+        (
+            ".text size",
+            fmt_per_type(per_type(baseline, text), fmt_size),
+            fmt_per_type(per_type(current, text), fmt_size),
+        ),
+        (
+            "LLVM lines",
+            fmt_per_type(per_type(baseline, lines), fmt_lines),
+            fmt_per_type(per_type(current, lines), fmt_lines),
+        ),
+    ] {
+        tx!(tx_w, "  per type, {}: {} → {}\n", label, old, new);
+        md!(md_w, "{}: {} → {}  \n", label, old, new);
+    }
+
+    Ok(())
+}
+
+/// Report what each facet feature combination costs, relative to the first one
+pub(crate) fn generate_feature_matrix_report(
+    scenario: &Scenario,
+    points: &[FeaturePoint],
+    tx_w: &mut String,
+    md_w: &mut String,
+) -> anyhow::Result<()> {
+    /// Value, followed by its difference from the reference unless it is the reference
+    fn with_delta(value: String, diff: Option<String>) -> String {
+        match diff {
+            Some(diff) => format!("{} ({})", value, diff),
+            None => value,
+        }
+    }
+
+    fn signed(diff: i64, fmt: impl Fn(u64) -> String) -> String {
+        match diff.cmp(&0) {
+            cmp::Ordering::Greater => format!("+{}", fmt(diff as u64)),
+            cmp::Ordering::Less => format!("-{}", fmt(diff.unsigned_abs())),
+            cmp::Ordering::Equal => "±0".to_owned(),
+        }
+    }
+
+    let Some(reference) = points.first() else {
+        return Ok(());
+    };
+
+    tx!(
+        tx_w,
+        "{}",
+        "Feature matrix (facet features)\n".bright_blue()
+    );
+    md!(md_w, "\n## 🎛️ Feature matrix\n\n");
+    md!(
+        md_w,
+        "`{}` built with each facet feature combination of `kitchensink/feature-matrix.txt`, compared to `{}`.\n\n",
+        scenario.bin,
+        reference.combination.name
+    );
+    md!(
+        md_w,
+        "| Combination | Changes | Build time | .text size | File size | LLVM lines | Crates |\n"
+    );
+    md!(
+        md_w,
+        "|-------------|---------|------------|------------|-----------|------------|-------:|\n"
+    );
+
+    for (i, point) in points.iter().enumerate() {
+        let is_reference = i == 0;
+        let delta = |diff: i64, fmt: fn(u64) -> String| (!is_reference).then(|| signed(diff, fmt));
+
+        let time = with_delta(
+            format!("{:.2}s", point.wall_duration.as_secs_f64()),
+            (!is_reference).then(|| {
+                let diff =
+                    point.wall_duration.as_secs_f64() - reference.wall_duration.as_secs_f64();
+                format!("{:+.2}s", diff)
+            }),
+        );
+        let text = with_delta(
+            format_bytes(point.text_size),
+            delta(
+                point.text_size as i64 - reference.text_size as i64,
+                format_bytes,
+            ),
+        );
+        let file = with_delta(
+            format_bytes(point.file_size),
+            delta(
+                point.file_size as i64 - reference.file_size as i64,
+                format_bytes,
+            ),
+        );
+        let lines = with_delta(
+            fmt_thousands(point.llvm_lines as isize),
+            delta(point.llvm_lines as i64 - reference.llvm_lines as i64, |n| {
+                fmt_thousands(n as isize)
+            }),
+        );
+        let crates = with_delta(
+            point.crates.to_string(),
+            delta(point.crates as i64 - reference.crates as i64, |n| {
+                n.to_string()
+            }),
+        );
+        let changes = point.combination.changes();
+        let changes = if changes.is_empty() {
+            "—".to_owned()
+        } else {
+            format!("`{}`", changes)
+        };
+
+        tx!(
+            tx_w,
+            "  {}: {}, .text {}, file {}, {} LLVM lines, {} crates\n",
+            point.combination.name.magenta(),
+            time,
+            text,
+            file,
+            lines,
+            crates
+        );
+        md!(
+            md_w,
+            "| `{}` | {} | {} | {} | {} | {} | {} |\n",
+            point.combination.name,
+            changes,
+            time,
+            text,
+            file,
+            lines,
+            crates
+        );
+    }
+    md!(md_w, "\n");
+
+    Ok(())
+}
+
+/// Report how facet-json handles malformed input, with serde_json as a reference
+pub(crate) fn generate_malformed_report(
+    baseline: &MalformedResult,
+    current: &MalformedResult,
+    serde: &MalformedResult,
+    tx_w: &mut String,
+    md_w: &mut String,
+) -> anyhow::Result<()> {
+    fn outcome_str(outcome: Option<&CaseOutcome>) -> String {
+        match outcome {
+            Some(CaseOutcome::Accepted) => "⚠️ accepted".to_owned(),
+            Some(CaseOutcome::Rejected(message)) => {
+                let mut s = format!("{} chars", message.text.chars().count());
+                if message.has_location {
+                    s.push_str(" 📍");
+                }
+                if message.mentions_culprit {
+                    s.push_str(" 🎯");
+                }
+                s
+            }
+            None => "—".to_owned(),
+        }
+    }
+
+    /// Aggregate message quality of a run, one line per metric
+    fn summary(result: &MalformedResult) -> [String; 4] {
+        let total = result.cases.len();
+        let messages: Vec<_> = result
+            .cases
+            .iter()
+            .filter_map(|c| match &c.outcome {
+                CaseOutcome::Rejected(message) => Some((c, message)),
+                CaseOutcome::Accepted => None,
+            })
+            .collect();
+        let with_culprit = result.cases.iter().filter(|c| c.culprit.is_some()).count();
+        let mut lengths: Vec<usize> = messages
+            .iter()
+            .map(|(_, m)| m.text.chars().count())
+            .collect();
+        lengths.sort_unstable();
+
+        [
+            format!("{}/{}", messages.len(), total),
+            format!(
+                "{}/{}",
+                messages.iter().filter(|(_, m)| m.has_location).count(),
+                messages.len()
+            ),
+            format!(
+                "{}/{}",
+                messages.iter().filter(|(_, m)| m.mentions_culprit).count(),
+                with_culprit
+            ),
+            lengths
+                .get(lengths.len() / 2)
+                .map_or("—".to_owned(), |n| format!("{} chars", n)),
+        ]
+    }
+
+    tx!(
+        tx_w,
+        "{}",
+        "Error reporting (malformed input)\n".bright_blue()
+    );
+    md!(md_w, "\n## 🚨 Error reporting\n\n");
+
+    let diff = current.text_size as i64 - baseline.text_size as i64;
+    let change_str = if diff > 0 {
+        format!("📈 +{}", format_bytes(diff as u64))
+    } else if diff < 0 {
+        format!("📉 -{}", format_bytes((-diff) as u64))
+    } else {
+        "➖ no change".to_owned()
+    };
+    tx!(
+        tx_w,
+        "  .text size: {} → {} ({}) (serde_json: {})\n",
+        format_bytes(baseline.text_size),
+        format_bytes(current.text_size).magenta(),
+        change_str,
+        format_bytes(serde.text_size)
+    );
+    md!(
+        md_w,
+        "`.text` size of the binary parsing the corpus and rendering every error: {} → {} ({}), serde_json: {}\n\n",
+        format_bytes(baseline.text_size),
+        format_bytes(current.text_size),
+        change_str,
+        format_bytes(serde.text_size)
+    );
+
+    md!(
+        md_w,
+        "| Case | Baseline | Current | serde_json (reference) |\n"
+    );
+    md!(
+        md_w,
+        "|------|----------|---------|------------------------|\n"
+    );
+    for case in &current.cases {
+        md!(
+            md_w,
+            "| `{}` | {} | {} | {} |\n",
+            case.name,
+            outcome_str(baseline.get(&case.name)),
+            outcome_str(Some(&case.outcome)),
+            outcome_str(serde.get(&case.name))
+        );
+    }
+
+    let labels = [
+        "rejected",
+        "with a location",
+        "naming the culprit",
+        "median length",
+    ];
+    let (old, new, reference) = (summary(baseline), summary(current), summary(serde));
+    for (i, label) in labels.iter().enumerate() {
+        tx!(
+            tx_w,
+            "  {}: {} → {} (serde_json: {})\n",
+            label,
+            old[i],
+            new[i].magenta(),
+            reference[i]
+        );
+        md!(
+            md_w,
+            "| **{}** | {} | {} | {} |\n",
+            label,
+            old[i],
+            new[i],
+            reference[i]
+        );
+    }
+    md!(
+        md_w,
+        "\n📍 points at a location in the input, 🎯 names the offending field or value.\n\n"
+    );
+
+    for case in &current.cases {
+        if let CaseOutcome::Accepted = case.outcome {
+            tx!(tx_w, "  {} {}\n", "accepted:".red(), case.name);
+        }
+    }
+
+    // Messages are only shown for the current build: that's what the PR changes
+    md!(md_w, "<details><summary>facet-json messages</summary>\n\n");
+    for case in &current.cases {
+        if let CaseOutcome::Rejected(message) = &case.outcome {
+            md!(
+                md_w,
+                "`{}`\n\n```\n{}\n```\n\n",
+                case.name,
+                message.text.trim_end()
+            );
+        }
+    }
+    md!(md_w, "</details>\n\n");
+
+    Ok(())
+}
+
+/// Format a short duration with an appropriate unit (e.g., 12.3 µs)
+fn fmt_nanos(d: Duration) -> String {
+    let nanos = d.as_nanos() as f64;
+    if nanos < 1_000.0 {
+        format!("{:.0} ns", nanos)
+    } else if nanos < 1_000_000.0 {
+        format!("{:.1} µs", nanos / 1_000.0)
+    } else if nanos < 1_000_000_000.0 {
+        format!("{:.2} ms", nanos / 1_000_000.0)
+    } else {
+        format!("{:.2} s", nanos / 1_000_000_000.0)
+    }
+}
+
+/// Change between two values present on both sides: marker, absolute and
+/// relative change, e.g. `⚠️ +1.2 KB (+6.1%)`. Growth past the warn threshold
+/// is recorded as a finding about `what`.
+fn change_cell(
+    what: impl FnOnce() -> String,
+    old: u64,
+    new: u64,
+    fmt: impl Fn(u64) -> String,
+    thresholds: &SeverityThresholds,
+    findings: &mut Vec<Finding>,
+) -> String {
+    let percent = fmt_percent(old, new);
+    match new.cmp(&old) {
+        cmp::Ordering::Greater => {
+            let severity = thresholds.classify(old, new);
+            let change = format!("+{}{}", fmt(new - old), percent);
+            if severity > Severity::Info {
+                findings.push(Finding {
+                    severity,
+                    what: what(),
+                    change: change.clone(),
+                });
+            }
+            format!("{} {}", severity.marker(), change)
+        }
+        cmp::Ordering::Less => format!("📉 -{}{}", fmt(old - new), percent),
+        cmp::Ordering::Equal => "➖ no change".to_owned(),
+    }
+}
+
+/// Cell of something only the current build has, e.g. a crate, of `size`. It
+/// has no baseline of its own, so it is measured against `total`, the size of
+/// what it is part of in the baseline: past the warn threshold, it is recorded
+/// as a finding about `what`.
+fn new_cell(
+    what: impl FnOnce() -> String,
+    size: u64,
+    total: u64,
+    fmt: impl Fn(u64) -> String,
+    thresholds: &SeverityThresholds,
+    findings: &mut Vec<Finding>,
+) -> String {
+    let severity = thresholds.classify(total, total + size);
+    if severity == Severity::Info {
+        return "🆕 NEW".to_owned();
+    }
+    let change = format!("🆕 +{}{}", fmt(size), fmt_percent(total, total + size));
+    findings.push(Finding {
+        severity,
+        what: what(),
+        change: change.clone(),
+    });
+    format!("{} {}", severity.marker(), change)
+}
+
+/// Relative change as a suffix, e.g. ` (+6.1%)`, if there is a baseline to compare to
+fn fmt_percent(old: u64, new: u64) -> String {
+    percent_change(old, new)
+        .map(|pct| format!(" ({:+.1}%)", pct))
+        .unwrap_or_default()
+}
+
 /// Format a byte count into a human-readable string (e.g., 1.2 MB)
 fn format_bytes(bytes: u64) -> String {
     const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
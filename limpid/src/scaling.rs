@@ -0,0 +1,209 @@
+//! Scaling curves of facet's build cost against the number of derived types
+//!
+//! The kitchensink has a fixed set of types, which can't tell whether compile
+//! time and code size grow linearly with the number of types. This generates
+//! a standalone crate with N synthetic structs and enums, all deriving
+//! `Facet` and round-tripped through facet-json, and builds it for several N.
+
+use anyhow::{anyhow, Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use std::fmt::Write;
+use std::time::Duration;
+use substance::BuildRunner;
+
+/// Name of the generated crate and of its binary
+const SYNTH_BIN: &str = "ks-synth";
+
+/// Shape of the generated type sets
+#[derive(Debug, Clone)]
+pub struct ScalingSettings {
+    /// Number of types to generate, one build per entry
+    pub sizes: Vec<usize>,
+    /// Number of fields per struct (struct variants get half as many)
+    pub fields: usize,
+    /// How many of the preceding types a type may contain, 0 for flat types
+    pub nesting: usize,
+}
+
+/// Build cost of the synthetic crate for a given number of types
+#[derive(Debug, Clone, Copy)]
+pub struct ScalingPoint {
+    pub types: usize,
+    pub wall_duration: Duration,
+    pub text_size: u64,
+    pub llvm_lines: usize,
+}
+
+/// Generate and build the synthetic crate for every size against the given
+/// facet checkout.
+pub fn run_scaling(facet_root: &Utf8Path, settings: &ScalingSettings) -> Result<Vec<ScalingPoint>> {
+    let mut points = Vec::new();
+
+    for &types in &settings.sizes {
+        println!(
+            "📐 Building {} synthetic types against {}...",
+            types, facet_root
+        );
+
+        // Removed once the build is analyzed
+        let tmp_dir = tempfile::Builder::new()
+            .prefix("limpid-synth")
+            .tempdir()
+            .context("Failed to create directory for the synthetic crate")?;
+        let crate_dir = Utf8Path::from_path(tmp_dir.path())
+            .ok_or_else(|| anyhow!("Temporary directory is not valid UTF-8"))?;
+
+        let manifest = write_synth_crate(crate_dir, facet_root, types, settings)?;
+        let context = BuildRunner::for_manifest(&manifest)
+            .arg("--bin")
+            .arg(SYNTH_BIN)
+            .arg("--release")
+            .run()
+            .map_err(|e| anyhow!("Build of {} synthetic types failed: {:?}", types, e))?;
+
+        points.push(ScalingPoint {
+            types,
+            wall_duration: context.wall_duration,
+            text_size: context.text_size.value(),
+            llvm_lines: context.num_llvm_lines(),
+        });
+    }
+
+    Ok(points)
+}
+
+/// Write the synthetic crate into `dir` and return the path to its manifest
+fn write_synth_crate(
+    dir: &Utf8Path,
+    facet_root: &Utf8Path,
+    types: usize,
+    settings: &ScalingSettings,
+) -> Result<Utf8PathBuf> {
+    let manifest = format!(
+        r#"[package]
+name = "{SYNTH_BIN}"
+version = "0.1.0"
+edition = "2024"
+publish = false
+
+# Not part of any enclosing workspace
+[workspace]
+
+[dependencies]
+facet = {{ path = "{facet}" }}
+facet-json = {{ path = "{facet_json}" }}
+"#,
+        facet = facet_root.join("facet"),
+        facet_json = facet_root.join("facet-json"),
+    );
+
+    std::fs::create_dir_all(dir.join("src"))?;
+    let manifest_path = dir.join("Cargo.toml");
+    std::fs::write(&manifest_path, manifest)
+        .with_context(|| format!("Failed to write {}", manifest_path))?;
+    std::fs::write(
+        dir.join("src").join("main.rs"),
+        generate_source(types, settings),
+    )?;
+
+    Ok(manifest_path)
+}
+
+/// Whether the `index`-th synthetic type is an enum (one in four) or a struct
+fn is_enum(index: usize) -> bool {
+    index % 4 == 3
+}
+
+fn type_name(index: usize) -> String {
+    if is_enum(index) {
+        format!("Enum{index}")
+    } else {
+        format!("Struct{index}")
+    }
+}
+
+/// Type of the `field`-th field of the `index`-th type.
+///
+/// Every third field refers to one of the `nesting` preceding types, behind
+/// an `Option<Box<_>>` or a `Vec`: these are empty by default, so the size of
+/// the round-tripped value doesn't grow exponentially with nesting. Other fields
+/// are scalars and standard containers.
+fn field_type(index: usize, field: usize, nesting: usize) -> String {
+    if nesting > 0 && index > 0 && field % 3 == 2 {
+        let target = type_name(index - 1 - (field / 3) % nesting.min(index));
+        return match (index + field) % 3 {
+            0 => format!("Option<Box<{target}>>"),
+            1 => format!("Vec<{target}>"),
+            _ => format!("Vec<Box<{target}>>"),
+        };
+    }
+
+    match (index + field) % 6 {
+        0 => "u32",
+        1 => "String",
+        2 => "bool",
+        3 => "f64",
+        4 => "Option<u32>",
+        _ => "Vec<String>",
+    }
+    .to_string()
+}
+
+/// Source of the synthetic crate: the types, a root struct holding one of
+/// each, and a `main` round-tripping it through facet-json
+fn generate_source(types: usize, settings: &ScalingSettings) -> String {
+    let mut src = String::new();
+    let fields = settings.fields;
+    let nesting = settings.nesting;
+
+    writeln!(
+        src,
+        "//! Generated by limpid: {types} types, {fields} fields per struct, nesting {nesting}\n"
+    )
+    .unwrap();
+    writeln!(src, "use facet::Facet;\n").unwrap();
+
+    for index in 0..types {
+        let name = type_name(index);
+        writeln!(src, "#[derive(Facet, Debug, Default)]").unwrap();
+        if is_enum(index) {
+            writeln!(src, "#[repr(u8)]").unwrap();
+            writeln!(src, "pub enum {name} {{").unwrap();
+            writeln!(src, "    #[default]").unwrap();
+            writeln!(src, "    Unit,").unwrap();
+            writeln!(src, "    Tuple(u32, String),").unwrap();
+            writeln!(src, "    Record {{").unwrap();
+            for field in 0..(fields / 2).max(1) {
+                let ty = field_type(index, field, nesting);
+                writeln!(src, "        f{field}: {ty},").unwrap();
+            }
+            writeln!(src, "    }},").unwrap();
+        } else {
+            writeln!(src, "pub struct {name} {{").unwrap();
+            for field in 0..fields {
+                let ty = field_type(index, field, nesting);
+                writeln!(src, "    pub f{field}: {ty},").unwrap();
+            }
+        }
+        writeln!(src, "}}\n").unwrap();
+    }
+
+    writeln!(src, "#[derive(Facet, Debug, Default)]").unwrap();
+    writeln!(src, "pub struct Root {{").unwrap();
+    for index in 0..types {
+        writeln!(src, "    pub t{index}: {},", type_name(index)).unwrap();
+    }
+    writeln!(src, "}}\n").unwrap();
+
+    src.push_str(
+        r#"fn main() {
+    let root = Root::default();
+    let json = facet_json::to_string(&root);
+    let back: Root = facet_json::from_str(&json).unwrap();
+    println!("{}", facet_json::to_string(&back).len());
+}
+"#,
+    );
+
+    src
+}
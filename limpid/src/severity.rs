@@ -0,0 +1,79 @@
+//! How alarming a change is
+//!
+//! Growth is classified by its size relative to the baseline: below the warn
+//! threshold it is informational, past it a warning, and past the critical
+//! threshold critical. The thresholds are set on the command line; the report
+//! marks each row accordingly and summarizes the warnings at the top.
+
+use std::fmt;
+
+/// Severity of a change, from least to most alarming
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warn,
+    Critical,
+}
+
+impl Severity {
+    /// Marker of growth of this severity
+    pub fn marker(self) -> &'static str {
+        match self {
+            Severity::Info => "📈",
+            Severity::Warn => "⚠️",
+            Severity::Critical => "🚨",
+        }
+    }
+}
+
+/// Relative growth, in percent, from which a change is a warning or critical
+#[derive(Debug, Clone, Copy)]
+pub struct SeverityThresholds {
+    pub warn: f64,
+    pub critical: f64,
+}
+
+impl Default for SeverityThresholds {
+    fn default() -> Self {
+        Self {
+            warn: 5.0,
+            critical: 20.0,
+        }
+    }
+}
+
+impl SeverityThresholds {
+    /// Severity of going from `old` to `new`. Shrinking is always informational.
+    pub fn classify(&self, old: u64, new: u64) -> Severity {
+        match percent_change(old, new) {
+            Some(pct) if pct >= self.critical => Severity::Critical,
+            Some(pct) if pct >= self.warn => Severity::Warn,
+            _ => Severity::Info,
+        }
+    }
+}
+
+impl fmt::Display for SeverityThresholds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "warn from +{}%, critical from +{}%",
+            self.warn, self.critical
+        )
+    }
+}
+
+/// Change from `old` to `new` in percent of `old`, if there was anything to compare to
+pub fn percent_change(old: u64, new: u64) -> Option<f64> {
+    (old > 0).then(|| (new as f64 - old as f64) / old as f64 * 100.0)
+}
+
+/// A change past the warn threshold, listed in the summary of the report
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub severity: Severity,
+    /// What changed, e.g. "crate `ks_types` size"
+    pub what: String,
+    /// The change, e.g. "+1.2 KB (+6.1%)"
+    pub change: String,
+}
@@ -0,0 +1,197 @@
+//! Building both sides with different rust toolchains
+//!
+//! With `--toolchains`, baseline and current are the same facet and limpid
+//! checkouts, built with two toolchains already installed locally, to tell
+//! whether a regression comes from the compiler rather than from facet.
+
+use anyhow::{anyhow, ensure, Context, Result};
+use camino::Utf8PathBuf;
+use std::ffi::OsString;
+use std::process::Command;
+
+use crate::git::run_command;
+
+/// A locally installed toolchain
+#[derive(Debug, Clone)]
+pub enum Toolchain {
+    /// A rustup toolchain name, e.g. `stable`, `1.87.0` or `nightly-2025-06-01`.
+    /// Selects both rustc and cargo.
+    Rustup(String),
+    /// Path to a rustc binary, used with the default cargo
+    Rustc(Utf8PathBuf),
+}
+
+impl Toolchain {
+    /// Parse a toolchain as given on the command line: anything that looks
+    /// like a path is a rustc binary, anything else a rustup toolchain name.
+    pub fn parse(s: &str) -> Result<Self> {
+        let s = s.trim();
+        ensure!(!s.is_empty(), "Empty toolchain name");
+        if s.contains(std::path::MAIN_SEPARATOR) || s.contains('/') {
+            let path = Utf8PathBuf::from(s);
+            ensure!(path.is_file(), "rustc not found at {}", path);
+            Ok(Toolchain::Rustc(path))
+        } else {
+            Ok(Toolchain::Rustup(s.trim_start_matches('+').to_string()))
+        }
+    }
+
+    /// The environment variable that selects this toolchain, and its value
+    fn env(&self) -> (&'static str, OsString) {
+        match self {
+            Toolchain::Rustup(name) => ("RUSTUP_TOOLCHAIN", name.into()),
+            Toolchain::Rustc(path) => ("RUSTC", path.as_os_str().into()),
+        }
+    }
+
+    /// `rustc --version` of this toolchain, which also checks it is installed
+    /// and that the builds actually pick it up
+    pub fn version(&self) -> Result<String> {
+        let version = self.query(self.rustc().arg("--version"))?;
+        if let Toolchain::Rustup(name) = self {
+            let mut cmd = Command::new("rustc");
+            let (var, value) = self.env();
+            cmd.env(var, value).arg("--version");
+            let in_path = self.query(&mut cmd)?;
+            ensure!(
+                in_path == version,
+                "The rustc in PATH ignores RUSTUP_TOOLCHAIN={} ({} instead of {}), \
+                 put rustup's proxies first in PATH",
+                name,
+                in_path,
+                version
+            );
+        }
+        Ok(version)
+    }
+
+    /// Host triple of this toolchain's rustc, from `rustc -vV`
+    pub fn host(&self) -> Result<String> {
+        let verbose = self.query(self.rustc().arg("-vV"))?;
+        Ok(verbose
+            .lines()
+            .find_map(|line| line.strip_prefix("host: "))
+            .unwrap_or("unknown")
+            .to_string())
+    }
+
+    /// `cargo --version` of this toolchain, or of the default cargo for a
+    /// rustc binary
+    pub fn cargo_version(&self) -> Result<String> {
+        let mut cmd = match self {
+            Toolchain::Rustup(name) => {
+                let mut cmd = Command::new("rustup");
+                cmd.args(["run", name, "cargo"]);
+                cmd
+            }
+            Toolchain::Rustc(_) => Command::new("cargo"),
+        };
+        self.query(cmd.arg("--version"))
+    }
+
+    /// A command running this toolchain's rustc. For rustup toolchains, rustup
+    /// is asked directly, as the `rustc` in PATH may not be its proxy and then
+    /// ignores RUSTUP_TOOLCHAIN.
+    fn rustc(&self) -> Command {
+        match self {
+            Toolchain::Rustup(name) => {
+                let mut cmd = Command::new("rustup");
+                cmd.args(["run", name, "rustc"]);
+                cmd
+            }
+            Toolchain::Rustc(path) => Command::new(path),
+        }
+    }
+
+    /// Standard output of a command of this toolchain
+    fn query(&self, cmd: &mut Command) -> Result<String> {
+        // Only toolchains that are already installed are compared
+        cmd.env("RUSTUP_AUTO_INSTALL", "0");
+
+        let output = run_command(cmd)?;
+        ensure!(
+            output.status.success(),
+            "Toolchain `{}` is not usable: {}",
+            self,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        Ok(String::from_utf8(output.stdout)
+            .context("Invalid UTF-8 in toolchain output")?
+            .trim()
+            .to_string())
+    }
+
+    /// A directory name for this toolchain's builds
+    fn dir_name(&self) -> String {
+        self.to_string()
+            .trim_matches('/')
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Display for Toolchain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Toolchain::Rustup(name) => write!(f, "{}", name),
+            Toolchain::Rustc(path) => write!(f, "{}", path),
+        }
+    }
+}
+
+/// Parse `--toolchains <baseline>,<current>`
+pub fn parse_toolchains(s: &str) -> Result<(Toolchain, Toolchain)> {
+    let (baseline, current) = s
+        .split_once(',')
+        .ok_or_else(|| anyhow!("Expected `<baseline>,<current>`, got `{}`", s))?;
+    Ok((Toolchain::parse(baseline)?, Toolchain::parse(current)?))
+}
+
+/// Run `f` with cargo and rustc from `toolchain`, or from the environment if
+/// there is none.
+///
+/// Like `RustcMemoryProbe`, this sets the variables on our own process, since
+/// `BuildRunner` doesn't let us set them on the cargo invocation. Both sides
+/// build the same manifest, so a `SUBSTANCE_TMP_DIR` gets a subdirectory per
+/// toolchain: `BuildRunner` would otherwise give them the same target
+/// directory, and the LLVM IR of one would be analyzed with the other.
+pub fn with_toolchain<T>(
+    toolchain: Option<&Toolchain>,
+    f: impl FnOnce() -> Result<T>,
+) -> Result<T> {
+    let Some(toolchain) = toolchain else {
+        return f();
+    };
+
+    let mut vars = vec![toolchain.env()];
+    if let Some(dir) = std::env::var_os("SUBSTANCE_TMP_DIR") {
+        let dir = std::path::Path::new(&dir).join(toolchain.dir_name());
+        vars.push(("SUBSTANCE_TMP_DIR", dir.into_os_string()));
+    }
+
+    let previous: Vec<(&str, Option<OsString>)> = vars
+        .iter()
+        .map(|(var, _)| (*var, std::env::var_os(var)))
+        .collect();
+    println!("🦀 Using toolchain {}", toolchain);
+    for (var, value) in vars {
+        std::env::set_var(var, value);
+    }
+
+    let result = f();
+
+    for (var, previous) in previous {
+        match previous {
+            Some(previous) => std::env::set_var(var, previous),
+            None => std::env::remove_var(var),
+        }
+    }
+    result
+}
@@ -0,0 +1,207 @@
+//! The `[workspace.dependencies]` of the kitchensink manifest
+//!
+//! The kitchensink pins the facet crates it builds (and ks-types) there, one
+//! inline table per line, e.g. `facet-json = { path = "../../facet/facet-json" }`.
+//! limpid reads those entries to find the facet checkout, and rewrites them to
+//! build another checkout or other features. Only what such lines use is
+//! understood: bare keys with string, boolean or array values, or a version
+//! string on its own.
+
+use anyhow::{anyhow, bail, Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+
+/// The kitchensink manifest, with its workspace dependencies parsed
+#[derive(Debug, Clone)]
+pub struct KitchensinkManifest {
+    pub path: Utf8PathBuf,
+    lines: Vec<String>,
+    pub dependencies: Vec<WorkspaceDependency>,
+}
+
+/// An entry of `[workspace.dependencies]`
+#[derive(Debug, Clone)]
+pub struct WorkspaceDependency {
+    pub name: String,
+    /// Keys of its inline table, in order, with their values as written
+    entries: Vec<(String, String)>,
+    /// Index of its line in the manifest
+    line: usize,
+    /// Whether it was changed since it was read, and its line must be rewritten
+    changed: bool,
+}
+
+impl KitchensinkManifest {
+    /// Read the manifest of the given kitchensink
+    pub fn read(kitchensink: &Utf8Path) -> Result<Self> {
+        let path = kitchensink.join("Cargo.toml");
+        let source =
+            std::fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path))?;
+
+        let lines: Vec<String> = source.lines().map(str::to_string).collect();
+        let mut dependencies = Vec::new();
+        let mut in_section = false;
+        for (index, line) in lines.iter().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.starts_with('[') {
+                in_section = trimmed == "[workspace.dependencies]";
+                continue;
+            }
+            if !in_section || trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let dependency = WorkspaceDependency::parse(trimmed, index)
+                .with_context(|| format!("{}:{}: can't parse `{}`", path, index + 1, trimmed))?;
+            dependencies.push(dependency);
+        }
+
+        Ok(Self {
+            path,
+            lines,
+            dependencies,
+        })
+    }
+
+    /// The dependency named `name`, to change it
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut WorkspaceDependency> {
+        self.dependencies.iter_mut().find(|d| d.name == name)
+    }
+
+    /// The manifest with the lines of the changed dependencies rewritten, and
+    /// everything else as it was read
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for (index, line) in self.lines.iter().enumerate() {
+            match self
+                .dependencies
+                .iter()
+                .find(|d| d.line == index && d.changed)
+            {
+                Some(dependency) => out.push_str(&dependency.to_line()),
+                None => out.push_str(line),
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl WorkspaceDependency {
+    fn parse(line: &str, index: usize) -> Result<Self> {
+        let (name, value) = line
+            .split_once('=')
+            .ok_or_else(|| anyhow!("expected `<name> = <spec>`"))?;
+        let name = name.trim().to_string();
+        let value = value.trim();
+
+        let entries = if let Some(table) = value
+            .strip_prefix('{')
+            .and_then(|rest| rest.strip_suffix('}'))
+        {
+            split_top_level(table)
+                .into_iter()
+                .map(|entry| {
+                    let (key, value) = entry
+                        .split_once('=')
+                        .ok_or_else(|| anyhow!("expected `<key> = <value>`, got `{}`", entry))?;
+                    Ok((key.trim().to_string(), value.trim().to_string()))
+                })
+                .collect::<Result<Vec<_>>>()?
+        } else if value.starts_with('"') {
+            vec![("version".to_string(), value.to_string())]
+        } else {
+            bail!("expected an inline table or a version string");
+        };
+
+        Ok(Self {
+            name,
+            entries,
+            line: index,
+            changed: false,
+        })
+    }
+
+    /// The value of `key`, as written
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Set `key` to `value`, written as is, keeping its place if it was there
+    pub fn set(&mut self, key: &str, value: String) {
+        match self.entries.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => *v = value,
+            None => self.entries.push((key.to_string(), value)),
+        }
+        self.changed = true;
+    }
+
+    /// Enable `features` on top of those it already enables
+    pub fn add_features(&mut self, features: &[String]) {
+        let mut all: Vec<String> = self
+            .get("features")
+            .and_then(|v| v.strip_prefix('[')?.strip_suffix(']'))
+            .map(|list| {
+                split_top_level(list)
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        for feature in features {
+            let quoted = format!("\"{}\"", feature);
+            if !all.contains(&quoted) {
+                all.push(quoted);
+            }
+        }
+        self.set("features", format!("[{}]", all.join(", ")));
+    }
+
+    /// The `path` it is found at, relative to the kitchensink unless absolute
+    pub fn path(&self) -> Option<&str> {
+        self.get("path").and_then(unquote)
+    }
+
+    /// Its line in the manifest, as an inline table
+    fn to_line(&self) -> String {
+        let entries: Vec<String> = self
+            .entries
+            .iter()
+            .map(|(k, v)| format!("{} = {}", k, v))
+            .collect();
+        format!("{} = {{ {} }}", self.name, entries.join(", "))
+    }
+}
+
+/// The contents of a TOML basic string, without its quotes
+fn unquote(value: &str) -> Option<&str> {
+    value.strip_prefix('"')?.strip_suffix('"')
+}
+
+/// Split `s` at the commas that are neither in a string nor in brackets,
+/// dropping empty parts (as left by a trailing comma)
+fn split_top_level(s: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '[' | '{' if !in_string => depth += 1,
+            ']' | '}' if !in_string => depth = depth.saturating_sub(1),
+            ',' if !in_string && depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect()
+}